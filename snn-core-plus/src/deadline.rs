@@ -0,0 +1,182 @@
+//! Wall-clock budgets for snn-core-plus.
+//!
+//! - Clock: pluggable microsecond time source; SystemClock for real loops, MockClock for tests.
+//! - StepBudgets::max_micros stops delivery once a tick has used its time slice (the clock
+//!   is read once per edge visit, and only while a deadline is set).
+//! - AdaptiveBudget: feedback controller that tunes max_edge_visits from measured step
+//!   durations so ticks meet a target period, and counts the work it gave up.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::runtime_plus::StepReport;
+
+/// Monotonic microsecond clock. `Send` so a runtime can move to another thread.
+pub trait Clock: Send {
+    fn now_micros(&self) -> u64;
+
+    /// Clock for a forked runtime (`SnnRuntimePlus::fork`); defaults to a new SystemClock.
+    fn fork(&self) -> Box<dyn Clock + Send> {
+        Box::new(SystemClock::new())
+    }
+}
+
+/// std::time::Instant based clock (microseconds since construction).
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        Self { origin: Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now_micros(&self) -> u64 {
+        self.origin.elapsed().as_micros() as u64
+    }
+}
+
+/// Manually driven clock for tests. Clones share the same time, so a test can keep a handle
+/// while the runtime owns another. With `auto_advance`, every read moves time forward, which
+/// models a fixed cost per clock check (i.e. per edge visit).
+#[derive(Clone, Default)]
+pub struct MockClock {
+    now: Arc<AtomicU64>,
+    auto_advance: u64,
+}
+
+impl MockClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clock that advances by `micros` on every read.
+    pub fn with_auto_advance(micros: u64) -> Self {
+        Self { now: Arc::default(), auto_advance: micros }
+    }
+
+    pub fn set(&self, micros: u64) {
+        self.now.store(micros, Ordering::Relaxed);
+    }
+
+    pub fn advance(&self, micros: u64) {
+        let _ = self.now.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |t| Some(t.saturating_add(micros)));
+    }
+}
+
+impl Clock for MockClock {
+    fn now_micros(&self) -> u64 {
+        let step = self.auto_advance;
+        self.now.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |t| Some(t.saturating_add(step))).unwrap_or_default()
+    }
+
+    /// Forks share this clock's time
+    fn fork(&self) -> Box<dyn Clock + Send> {
+        Box::new(self.clone())
+    }
+}
+
+/// Counters of what the adaptive controller gave up.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AdaptiveStats {
+    /// Ticks whose duration exceeded the target period
+    pub overrun_ticks: u64,
+    /// Ticks in which the controller's edge limit stopped delivery
+    pub limited_ticks: u64,
+    /// Work dropped or deferred in limited ticks (deliveries + spikes)
+    pub shed_work: u64,
+}
+
+/// Feedback controller for `max_edge_visits`.
+///
+/// After every tick the limit is rescaled toward `limit * target / elapsed` (multiplicative
+/// decrease on overrun); when a tick was cut short but finished early, the limit grows by
+/// `limit * (target - elapsed) / target`, at least 1 edge. The limit stays in [min, max].
+#[derive(Clone, Debug)]
+pub struct AdaptiveBudget {
+    target_micros: u64,
+    min_edges: usize,
+    max_edges: usize,
+    limit: usize,
+    stats: AdaptiveStats,
+}
+
+impl AdaptiveBudget {
+    pub fn new(target_micros: u64, min_edges: usize, max_edges: usize) -> Self {
+        let min_edges = min_edges.max(1);
+        let max_edges = max_edges.max(min_edges);
+        Self { target_micros: target_micros.max(1), min_edges, max_edges, limit: max_edges, stats: AdaptiveStats::default() }
+    }
+
+    /// Current edge-visit limit
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    pub fn target_micros(&self) -> u64 {
+        self.target_micros
+    }
+
+    pub fn stats(&self) -> AdaptiveStats {
+        self.stats
+    }
+
+    /// Feed the report of the tick that just ran (its `elapsed_micros` and budget flags).
+    pub fn observe(&mut self, report: &StepReport) {
+        let elapsed = report.elapsed_micros;
+        let target = self.target_micros;
+        if report.edge_budget_hit {
+            self.stats.limited_ticks += 1;
+            self.stats.shed_work += (report.dropped + report.deferred) as u64;
+        }
+        let limit = self.limit as u64;
+        let next = if elapsed > target {
+            self.stats.overrun_ticks += 1;
+            limit * target / elapsed
+        } else if report.edge_budget_hit {
+            limit + (limit * (target - elapsed) / target).max(1)
+        } else {
+            limit
+        };
+        self.limit = (next as usize).clamp(self.min_edges, self.max_edges);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(elapsed_micros: u64, edge_budget_hit: bool) -> StepReport {
+        StepReport { elapsed_micros, edge_budget_hit, dropped: 3, ..Default::default() }
+    }
+
+    #[test]
+    fn test_mock_clock_shares_time() {
+        let clock = MockClock::with_auto_advance(2);
+        let handle = clock.clone();
+        assert_eq!(clock.now_micros(), 0);
+        handle.advance(10);
+        assert_eq!(clock.now_micros(), 12);
+    }
+
+    #[test]
+    fn test_controller_backs_off_and_recovers() {
+        let mut ctl = AdaptiveBudget::new(100, 10, 1000);
+        ctl.observe(&report(400, false));
+        assert_eq!(ctl.limit(), 250);
+        ctl.observe(&report(50, true));
+        assert_eq!(ctl.limit(), 375);
+        ctl.observe(&report(100_000, true));
+        assert_eq!(ctl.limit(), 10);
+        assert_eq!(ctl.stats(), AdaptiveStats { overrun_ticks: 2, limited_ticks: 2, shed_work: 6 });
+    }
+}
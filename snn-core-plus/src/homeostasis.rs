@@ -0,0 +1,320 @@
+//! Homeostatic plasticity for snn-core-plus (feature "plasticity").
+//!
+//! - Trait HomeostaticRule: observes spikes every tick and applies an update once per period,
+//!   so the per-tick cost is O(spikes) and the O(N + E) work is amortized over the period.
+//! - IntrinsicPlasticity: moves each neuron's threshold toward a target firing rate.
+//! - SynapticScaling: multiplicatively scales incoming weights toward a target firing rate.
+//! - WeightNormalization: rescales incoming weights so each target's total input is constant.
+//!
+//! Both weight rules act on excitatory edges only; inhibitory and modulatory edges keep
+//! their weights and do not count toward a target's input sum.
+//!
+//! Rates are measured in spikes per tick over the last period. Hyperedges share one weight
+//! across all of their targets, so per-target factors are averaged over an edge's targets
+//! (exact for single-target edges). All arithmetic is Q16.16 i32 like snn-core.
+
+use snn_core::{fixed_mul, neuron_id, to_fixed, EdgeArena, EdgeKind, Fixed, SpikeEvent, SCALE};
+
+use crate::neuron_store::NeuronStore;
+
+/// Mutable network state handed to a homeostatic update.
+pub struct HomeostasisView<'a> {
+    pub neurons: &'a mut NeuronStore,
+    /// Compiled edges; `edges.incoming(n)` lists the edges arriving at neuron n
+    pub edges: &'a mut EdgeArena,
+}
+
+/// Homeostatic mechanism that runs alongside a PlasticityRule.
+pub trait HomeostaticRule {
+    /// Update period in ticks (`apply` runs once every `period` ticks)
+    fn period(&self) -> u64;
+
+    /// Record the spikes of one tick (called every tick; keep it cheap)
+    fn observe(&mut self, spikes: &[SpikeEvent]);
+
+    /// Apply the update for the window that just ended and start a new window
+    fn apply(&mut self, net: HomeostasisView<'_>);
+
+    /// Discard the current window (`SnnRuntimePlus::reset_state`); the default keeps it.
+    fn reset(&mut self) {}
+
+    /// Independent copy for `SnnRuntimePlus::fork`; None (the default) makes the fork fail.
+    fn fork(&self) -> Option<Box<dyn HomeostaticRule>> {
+        None
+    }
+}
+
+/// Spike counts over the current window, converted to Q16.16 spikes/tick on read.
+#[derive(Clone, Default)]
+struct RateWindow {
+    counts: Vec<u32>,
+}
+
+impl RateWindow {
+    fn observe(&mut self, spikes: &[SpikeEvent]) {
+        for sp in spikes {
+            let idx = sp.neuron_id as usize;
+            if self.counts.len() <= idx {
+                self.counts.resize(idx + 1, 0);
+            }
+            self.counts[idx] = self.counts[idx].saturating_add(1);
+        }
+    }
+
+    fn rate(&self, id: usize, period: u64) -> Fixed {
+        let count = *self.counts.get(id).unwrap_or(&0) as i64;
+        ((count * SCALE as i64) / period.max(1) as i64) as Fixed
+    }
+
+    fn reset(&mut self) {
+        self.counts.iter_mut().for_each(|c| *c = 0);
+    }
+}
+
+/// Scale each excitatory edge by the mean of its targets' factors (Q16.16), skipping
+/// neutral edges.
+fn scale_edges(edges: &mut EdgeArena, factors: &[Fixed]) {
+    for edge in edges.iter_mut() {
+        if edge.kind != EdgeKind::Excitatory || edge.targets.is_empty() {
+            continue;
+        }
+        let sum: i64 = edge
+            .targets
+            .iter()
+            .map(|&t| *factors.get(t as usize).unwrap_or(&SCALE) as i64)
+            .sum();
+        let mean = (sum / edge.targets.len() as i64) as Fixed;
+        if mean != SCALE {
+            *edge.weight = fixed_mul(*edge.weight, mean);
+        }
+    }
+}
+
+/// Intrinsic plasticity: threshold += eta * (rate - target_rate), clamped to [min, max].
+#[derive(Clone)]
+pub struct IntrinsicPlasticity {
+    period: u64,
+    target_rate: Fixed,
+    eta: Fixed,
+    threshold_min: Fixed,
+    threshold_max: Fixed,
+    window: RateWindow,
+}
+
+impl IntrinsicPlasticity {
+    /// `target_rate` is in spikes per tick; `eta` is the threshold change per unit rate error.
+    pub fn new(period: u64, target_rate: f32, eta: f32, threshold_min: f32, threshold_max: f32) -> Self {
+        Self {
+            period: period.max(1),
+            target_rate: to_fixed(target_rate),
+            eta: to_fixed(eta),
+            threshold_min: to_fixed(threshold_min),
+            threshold_max: to_fixed(threshold_max),
+            window: RateWindow::default(),
+        }
+    }
+}
+
+impl HomeostaticRule for IntrinsicPlasticity {
+    fn period(&self) -> u64 {
+        self.period
+    }
+
+    fn observe(&mut self, spikes: &[SpikeEvent]) {
+        self.window.observe(spikes);
+    }
+
+    fn apply(&mut self, net: HomeostasisView<'_>) {
+        for (i, th) in net.neurons.threshold_mut().iter_mut().enumerate() {
+            let err = self.window.rate(i, self.period).saturating_sub(self.target_rate);
+            *th = th.saturating_add(fixed_mul(self.eta, err)).max(self.threshold_min).min(self.threshold_max);
+        }
+        self.window.reset();
+    }
+
+    fn reset(&mut self) {
+        self.window.reset();
+    }
+
+    fn fork(&self) -> Option<Box<dyn HomeostaticRule>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Synaptic scaling: incoming excitatory weights *= 1 + eta * (target_rate - rate), factor
+/// floored at 0.
+#[derive(Clone)]
+pub struct SynapticScaling {
+    period: u64,
+    target_rate: Fixed,
+    eta: Fixed,
+    window: RateWindow,
+    factors: Vec<Fixed>,
+}
+
+impl SynapticScaling {
+    /// `target_rate` is in spikes per tick; `eta` is the relative scaling per unit rate error.
+    pub fn new(period: u64, target_rate: f32, eta: f32) -> Self {
+        Self {
+            period: period.max(1),
+            target_rate: to_fixed(target_rate),
+            eta: to_fixed(eta),
+            window: RateWindow::default(),
+            factors: Vec::new(),
+        }
+    }
+}
+
+impl HomeostaticRule for SynapticScaling {
+    fn period(&self) -> u64 {
+        self.period
+    }
+
+    fn observe(&mut self, spikes: &[SpikeEvent]) {
+        self.window.observe(spikes);
+    }
+
+    fn apply(&mut self, net: HomeostasisView<'_>) {
+        self.factors.clear();
+        for i in 0..net.neurons.len() {
+            let err = self.target_rate.saturating_sub(self.window.rate(i, self.period));
+            self.factors.push(SCALE.saturating_add(fixed_mul(self.eta, err)).max(0));
+        }
+        scale_edges(net.edges, &self.factors);
+        self.window.reset();
+    }
+
+    fn reset(&mut self) {
+        self.window.reset();
+    }
+
+    fn fork(&self) -> Option<Box<dyn HomeostaticRule>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Weight normalization: rescales incoming excitatory weights so their sum per target equals
+/// `target_sum`. Targets whose excitatory sum is not positive are left unchanged.
+#[derive(Clone)]
+pub struct WeightNormalization {
+    period: u64,
+    target_sum: Fixed,
+    factors: Vec<Fixed>,
+}
+
+impl WeightNormalization {
+    pub fn new(period: u64, target_sum: f32) -> Self {
+        Self {
+            period: period.max(1),
+            target_sum: to_fixed(target_sum),
+            factors: Vec::new(),
+        }
+    }
+}
+
+impl HomeostaticRule for WeightNormalization {
+    fn period(&self) -> u64 {
+        self.period
+    }
+
+    fn observe(&mut self, _spikes: &[SpikeEvent]) {}
+
+    fn apply(&mut self, net: HomeostasisView<'_>) {
+        self.factors.clear();
+        for i in 0..net.neurons.len() {
+            let edges = &*net.edges;
+            let sum: i64 = edges
+                .incoming(neuron_id(i))
+                .iter()
+                .filter(|&&e| edges.kind(e) == EdgeKind::Excitatory)
+                .map(|&e| edges.weight(e) as i64)
+                .sum();
+            let factor = if sum > 0 {
+                (((self.target_sum as i64) << 16) / sum).min(i32::MAX as i64) as Fixed
+            } else {
+                SCALE
+            };
+            self.factors.push(factor);
+        }
+        scale_edges(net.edges, &self.factors);
+    }
+
+    fn fork(&self) -> Option<Box<dyn HomeostaticRule>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snn_core::{from_fixed, HyperEdge, Modulation, NeuronId};
+
+    fn net() -> (NeuronStore, EdgeArena) {
+        let mut neurons = NeuronStore::default();
+        for _ in 0..3 {
+            neurons.push(1.0);
+        }
+        let edges = EdgeArena::from_edges(&[
+            HyperEdge { id: 0, sources: vec![0], targets: vec![2], weight: to_fixed(0.25), delay: 1, kind: EdgeKind::Excitatory },
+            HyperEdge { id: 1, sources: vec![1], targets: vec![2], weight: to_fixed(0.75), delay: 1, kind: EdgeKind::Excitatory },
+        ]);
+        (neurons, edges)
+    }
+
+    fn spikes(ids: &[NeuronId]) -> Vec<SpikeEvent> {
+        ids.iter().map(|&neuron_id| SpikeEvent::spike(neuron_id, 0)).collect()
+    }
+
+    #[test]
+    fn test_intrinsic_raises_threshold_of_busy_neuron() {
+        let (mut neurons, mut edges) = net();
+        let mut ip = IntrinsicPlasticity::new(10, 0.1, 1.0, 0.5, 2.0);
+        for _ in 0..10 {
+            ip.observe(&spikes(&[0]));
+        }
+        ip.apply(HomeostasisView { neurons: &mut neurons, edges: &mut edges });
+        // neuron 0 fired at 1.0/tick (err +0.9), neuron 1 silent (err -0.1)
+        assert!((from_fixed(neurons.view().threshold[0]) - 1.9).abs() < 1e-3);
+        assert!((from_fixed(neurons.view().threshold[1]) - 0.9).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_scaling_boosts_silent_target() {
+        let (mut neurons, mut edges) = net();
+        let mut sc = SynapticScaling::new(4, 0.5, 1.0);
+        sc.apply(HomeostasisView { neurons: &mut neurons, edges: &mut edges });
+        // target 2 silent: factor 1.5
+        assert!((from_fixed(edges.weight(0)) - 0.375).abs() < 1e-3);
+        assert!((from_fixed(edges.weight(1)) - 1.125).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_normalization_preserves_ratios() {
+        let (mut neurons, mut edges) = net();
+        let mut wn = WeightNormalization::new(1, 2.0);
+        wn.apply(HomeostasisView { neurons: &mut neurons, edges: &mut edges });
+        assert!((from_fixed(edges.weight(0)) - 0.5).abs() < 1e-3);
+        assert!((from_fixed(edges.weight(1)) - 1.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_weight_rules_leave_inhibitory_and_modulatory_edges_alone() {
+        let (mut neurons, edges) = net();
+        let mut all = edges.to_edges();
+        all.push(HyperEdge { id: 2, sources: vec![1], targets: vec![2], weight: to_fixed(-0.5), delay: 1, kind: EdgeKind::Inhibitory });
+        all.push(HyperEdge { id: 3, sources: vec![0], targets: vec![2], weight: to_fixed(0.5), delay: 1, kind: EdgeKind::Modulatory(Modulation::Gain) });
+        let mut edges = EdgeArena::from_edges(&all);
+
+        // The excitatory sum stays 1.0, so the factor is 2.0 despite the other kinds
+        WeightNormalization::new(1, 2.0).apply(HomeostasisView { neurons: &mut neurons, edges: &mut edges });
+        assert!((from_fixed(edges.weight(0)) - 0.5).abs() < 1e-3);
+        assert!((from_fixed(edges.weight(1)) - 1.5).abs() < 1e-3);
+        assert_eq!(edges.weight(2), to_fixed(-0.5));
+        assert_eq!(edges.weight(3), to_fixed(0.5));
+
+        SynapticScaling::new(4, 0.5, 1.0).apply(HomeostasisView { neurons: &mut neurons, edges: &mut edges });
+        assert!((from_fixed(edges.weight(0)) - 0.75).abs() < 1e-3);
+        assert_eq!(edges.weight(2), to_fixed(-0.5));
+        assert_eq!(edges.weight(3), to_fixed(0.5));
+    }
+}
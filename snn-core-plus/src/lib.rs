@@ -4,15 +4,33 @@
 //! Additions:
 //! - Adjacency index (source -> edges) to avoid O(E) scans
 //! - Budgeted stepping API surface (hooks present; conservative defaults)
-//! - Optional plasticity trait (feature "plasticity")
+//! - Structure-of-arrays neuron storage
+//! - Short-term synaptic plasticity (Tsodyks–Markram) per hyperedge
+//! - Current- and conductance-based synapse kernels selectable per hyperedge
+//! - Wall-clock step deadlines with a pluggable Clock and an adaptive budget controller
+//! - Typed edges from snn-core (modulatory delivery, optional Dale's-law clamping)
+//! - Optional plasticity trait, homeostatic and structural plasticity (feature "plasticity")
 //! - Optional embedded/no_std modules behind feature "embedded"
 //!
 //! This crate composes snn-core's types and reuses its event/time semantics.
 
 #[cfg(feature = "std")]
 pub mod runtime_plus;
+pub mod short_term;
+#[cfg(feature = "std")]
+pub mod neuron_store;
+#[cfg(feature = "std")]
+pub mod synapse;
+#[cfg(feature = "std")]
+pub mod deadline;
 #[cfg(feature = "plasticity")]
 pub mod plasticity;
+#[cfg(feature = "plasticity")]
+pub mod plasticity_stack;
+#[cfg(feature = "plasticity")]
+pub mod homeostasis;
+#[cfg(feature = "plasticity")]
+pub mod structural;
 
 // Embedded/no_std modules (only compiled when feature = "embedded")
 #[cfg(feature = "embedded")]
@@ -32,7 +50,20 @@ pub mod error;
 // Re-exports
 pub use error::{EmbeddedError, EmbeddedResult};
 #[cfg(feature = "std")]
-pub use runtime_plus::{SnnRuntimePlus, StepBudgets};
+pub use runtime_plus::{ClassStats, EdgeOptions, ForkError, Lateness, OverflowPolicy, SnnRuntimePlus, StepBudgets, StepReport};
+pub use short_term::{StpParams, StpState};
+#[cfg(feature = "std")]
+pub use deadline::{AdaptiveBudget, AdaptiveStats, Clock, MockClock, SystemClock};
+#[cfg(feature = "std")]
+pub use neuron_store::{NeuronStore, NeuronsView};
+#[cfg(feature = "std")]
+pub use synapse::{SynapseBank, SynapseChannel, SynapseKind};
 
 #[cfg(feature = "plasticity")]
-pub use plasticity::{PlasticityRule, QuantizedStdp};
\ No newline at end of file
+pub use plasticity::{PairStdp, PlasticityRule, QuantizedStdp, RewardModulatedStdp, StdpPairing, TripletStdp};
+#[cfg(feature = "plasticity")]
+pub use homeostasis::{HomeostasisView, HomeostaticRule, IntrinsicPlasticity, SynapticScaling, WeightNormalization};
+#[cfg(feature = "plasticity")]
+pub use structural::{StructuralConfig, StructuralEvent, StructuralPlasticity};
+#[cfg(feature = "plasticity")]
+pub use plasticity_stack::{rule_by_name, IdSet, PlasticityStack, RuleScope, StackEntry, StackRule};
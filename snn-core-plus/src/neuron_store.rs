@@ -0,0 +1,225 @@
+//! Structure-of-arrays neuron storage for SnnRuntimePlus
+//!
+//! Membrane, threshold, refractory and gain values live in separate arrays (index == neuron
+//! id), so sparse injection touches one membrane word per target and population-wide updates
+//! such as a leak are plain loops over a slice. Semantics match `snn_core::Neuron` exactly;
+//! `NeuronsView` and `get` give the per-neuron form back.
+
+use snn_core::{fixed_mul, neuron_id, to_fixed, Fixed, Modulation, Neuron, NeuronId, NeuronParam, SCALE};
+
+#[derive(Clone, Debug, Default)]
+pub struct NeuronStore {
+    membrane: Vec<Fixed>,
+    threshold: Vec<Fixed>,
+    refractory_until: Vec<u64>,
+    gain: Vec<Fixed>,
+}
+
+/// Read-only view of a `NeuronStore`: one slice per field, indexed by neuron id.
+#[derive(Clone, Copy, Debug)]
+pub struct NeuronsView<'a> {
+    pub membrane: &'a [Fixed],
+    pub threshold: &'a [Fixed],
+    pub refractory_until: &'a [u64],
+    pub gain: &'a [Fixed],
+}
+
+impl NeuronsView<'_> {
+    pub fn len(&self) -> usize {
+        self.membrane.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.membrane.is_empty()
+    }
+
+    /// Copy of one neuron in the `snn_core::Neuron` layout.
+    pub fn get(&self, id: NeuronId) -> Option<Neuron> {
+        let i = id as usize;
+        (i < self.len()).then(|| Neuron {
+            id,
+            membrane: self.membrane[i],
+            threshold: self.threshold[i],
+            refractory_until: self.refractory_until[i],
+            gain: self.gain[i],
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Neuron> + '_ {
+        (0..self.len()).filter_map(|i| self.get(neuron_id(i)))
+    }
+}
+
+impl From<Vec<Neuron>> for NeuronStore {
+    /// Neurons are stored by position (their `id` fields are expected to match).
+    fn from(neurons: Vec<Neuron>) -> Self {
+        let mut store = Self::default();
+        for n in neurons {
+            store.membrane.push(n.membrane);
+            store.threshold.push(n.threshold);
+            store.refractory_until.push(n.refractory_until);
+            store.gain.push(n.gain);
+        }
+        store
+    }
+}
+
+impl NeuronStore {
+    /// Append a neuron at rest; returns its id.
+    pub fn push(&mut self, threshold: f32) -> NeuronId {
+        let id = neuron_id(self.membrane.len());
+        self.membrane.push(0);
+        self.threshold.push(to_fixed(threshold));
+        self.refractory_until.push(0);
+        self.gain.push(SCALE);
+        id
+    }
+
+    pub fn len(&self) -> usize {
+        self.membrane.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.membrane.is_empty()
+    }
+
+    #[inline]
+    pub fn contains(&self, id: NeuronId) -> bool {
+        (id as usize) < self.membrane.len()
+    }
+
+    pub fn view(&self) -> NeuronsView<'_> {
+        NeuronsView {
+            membrane: &self.membrane,
+            threshold: &self.threshold,
+            refractory_until: &self.refractory_until,
+            gain: &self.gain,
+        }
+    }
+
+    pub fn get(&self, id: NeuronId) -> Option<Neuron> {
+        self.view().get(id)
+    }
+
+    pub fn membrane_mut(&mut self) -> &mut [Fixed] {
+        &mut self.membrane
+    }
+
+    pub fn threshold_mut(&mut self) -> &mut [Fixed] {
+        &mut self.threshold
+    }
+
+    pub fn refractory_until_mut(&mut self) -> &mut [u64] {
+        &mut self.refractory_until
+    }
+
+    pub fn gain_mut(&mut self) -> &mut [Fixed] {
+        &mut self.gain
+    }
+
+    /// Zero membranes and refractory periods (see `SnnRuntime::reset_state`).
+    pub fn reset_state(&mut self) {
+        self.membrane.fill(0);
+        self.refractory_until.fill(0);
+    }
+
+    /// `Neuron::inject` for neuron `id`; unknown ids never fire.
+    #[inline]
+    pub fn inject(&mut self, id: NeuronId, input: Fixed, time: u64) -> bool {
+        let i = id as usize;
+        if i >= self.membrane.len() || time < self.refractory_until[i] {
+            return false;
+        }
+        let input = self.gained(i, input);
+        let v = self.membrane[i].saturating_add(input);
+        if v >= self.threshold[i] {
+            self.membrane[i] = 0;
+            return true;
+        }
+        self.membrane[i] = v;
+        false
+    }
+
+    /// `Neuron::inject_spread` for neuron `id`.
+    pub fn inject_spread(&mut self, id: NeuronId, input: Fixed, time: u64) -> Option<u16> {
+        let i = id as usize;
+        let before = *self.membrane.get(i)?;
+        if !self.inject(id, input, time) {
+            return None;
+        }
+        let input = self.gained(i, input);
+        let needed = i64::from(self.threshold[i]) - i64::from(before);
+        if input <= 0 || needed <= 0 {
+            return Some(0);
+        }
+        Some(((needed << 16) / i64::from(input)).min(u16::MAX as i64) as u16)
+    }
+
+    /// `Neuron::set_param` for neuron `id`; unknown ids are ignored.
+    pub fn set_param(&mut self, id: NeuronId, param: NeuronParam, value: Fixed) {
+        let i = id as usize;
+        if i >= self.membrane.len() {
+            return;
+        }
+        match param {
+            NeuronParam::Membrane => self.membrane[i] = value,
+            NeuronParam::Threshold => self.threshold[i] = value,
+            NeuronParam::Gain => self.gain[i] = value.max(0),
+        }
+    }
+
+    /// `Neuron::modulate` for neuron `id`; unknown ids are ignored.
+    pub fn modulate(&mut self, id: NeuronId, modulation: Modulation, amount: Fixed) {
+        let i = id as usize;
+        if i >= self.membrane.len() {
+            return;
+        }
+        match modulation {
+            Modulation::Gain => self.gain[i] = self.gain[i].saturating_add(amount).max(0),
+            Modulation::Threshold => self.threshold[i] = self.threshold[i].saturating_add(amount),
+        }
+    }
+
+    #[inline]
+    fn gained(&self, i: usize, input: Fixed) -> Fixed {
+        if self.gain[i] == SCALE {
+            input
+        } else {
+            fixed_mul(input, self.gain[i])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_matches_neuron_semantics() {
+        let mut store = NeuronStore::default();
+        let mut reference = Vec::new();
+        for (i, th) in [1.0f32, 0.5, 2.0].into_iter().enumerate() {
+            store.push(th);
+            reference.push(Neuron::new(neuron_id(i), th));
+        }
+        store.modulate(1, Modulation::Gain, to_fixed(0.5));
+        reference[1].modulate(Modulation::Gain, to_fixed(0.5));
+        store.set_param(2, NeuronParam::Membrane, to_fixed(1.5));
+        reference[2].set_param(NeuronParam::Membrane, to_fixed(1.5));
+        reference[0].refractory_until = 3;
+        store.refractory_until_mut()[0] = 3;
+
+        for t in 0..6u64 {
+            for id in 0..3 {
+                let input = to_fixed(0.3);
+                assert_eq!(store.inject_spread(id, input, t), reference[id as usize].inject_spread(input, t));
+            }
+        }
+        for (got, want) in store.view().iter().zip(&reference) {
+            assert_eq!((got.membrane, got.threshold, got.gain), (want.membrane, want.threshold, want.gain));
+        }
+        // Unknown ids are ignored
+        assert!(!store.inject(7, SCALE * 10, 0));
+        assert_eq!(store.get(7).map(|n| n.id), None);
+    }
+}
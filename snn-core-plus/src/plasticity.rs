@@ -1,12 +1,23 @@
 //! Plasticity primitives for snn-core-plus (feature "plasticity").
 //!
-//! - Trait PlasticityRule: hooks for decay and pre/post spikes, with edge weight update.
+//! - Trait PlasticityRule: hooks for decay and pre/post spikes, with edge weight updates
+//!   on both sides of a pairing (LTP when the post neuron spikes, LTD when the pre neuron spikes).
 //! - QuantizedStdp: minimal STDP with fixed-point style parameters (Q16.16 i32),
 //!   maintaining per-neuron pre/post traces and clamping weights.
+//! - RewardModulatedStdp: R-STDP that accumulates STDP updates into per-edge
+//!   eligibility traces and only changes weights when a reward signal arrives.
+//! - PairStdp: pair-based STDP with nearest-neighbour or all-to-all spike pairing.
+//! - TripletStdp: triplet STDP (Pfister & Gerstner 2006, all-to-all interaction).
+//!
+//! PairStdp and TripletStdp keep event-timed traces: instead of decaying every tick they
+//! store (value, last update time) and apply `alpha^Δt` on read, so weight changes follow
+//! the analytic `A * exp(-Δt / tau)` curves.
 //!
 //! This module intentionally uses the same fixed representation as snn-core (i32 Q16.16)
 //! and avoids external dependencies.
 
+use snn_core::{fixed_pow, NeuronId};
+
 /// Q16.16 helpers (mirrors snn-core fixed.rs constants/behavior)
 const FRAC_BITS: i32 = 16;
 const ONE: i32 = 1 << FRAC_BITS;
@@ -17,6 +28,7 @@ fn fx_from_f32(x: f32) -> i32 {
 }
 
 #[inline]
+#[cfg(test)]
 fn fx_to_f32(x: i32) -> f32 {
     (x as f32) / (ONE as f32)
 }
@@ -36,25 +48,93 @@ fn fx_sub_sat(a: i32, b: i32) -> i32 {
     a.saturating_sub(b)
 }
 
+/// Decay towards zero symmetrically (plain `fx_mul` floors negative values at -1)
+#[inline]
+fn fx_decay(x: i32, alpha: i32) -> i32 {
+    if x < 0 {
+        -fx_mul(x.saturating_neg(), alpha)
+    } else {
+        fx_mul(x, alpha)
+    }
+}
+
+#[inline]
+fn fx_clamp(x: i32, lo: i32, hi: i32) -> i32 {
+    x.max(lo).min(hi)
+}
+
+/// Per-tick decay factor exp(-1 / tau) for a time constant given in ticks
+#[inline]
+fn fx_alpha_from_tau(tau: f32) -> i32 {
+    fx_from_f32((-1.0 / tau).exp())
+}
+
 /// Plasticity rule interface
 pub trait PlasticityRule {
     /// Decay internal state each tick (e.g., exponential decay of traces)
     fn decay(&mut self);
 
+    /// Decay over `ticks` quiet ticks at once (fast-forward). The default calls `decay`
+    /// `ticks` times; the built-in rules decay in closed form by `alpha^ticks`
+    /// (`snn_core::fixed_pow`), which rounds once per squaring instead of once per tick.
+    /// For a trace of magnitude up to 1.0 the two differ by less than
+    /// `1 / (1 - alpha) + 2 * log2(ticks) + 2` units of 2^-16.
+    fn decay_ticks(&mut self, ticks: u64) {
+        for _ in 0..ticks {
+            self.decay();
+        }
+    }
+
     /// Called when a pre-synaptic neuron spikes at time `t`
-    fn on_pre_spike(&mut self, pre: u32, t: u64);
+    fn on_pre_spike(&mut self, pre: NeuronId, t: u64);
 
     /// Called when a post-synaptic neuron spikes at time `t`
-    fn on_post_spike(&mut self, post: u32, t: u64);
+    fn on_post_spike(&mut self, post: NeuronId, t: u64);
+
+    /// Pre side of a pairing: `pre` spiked at `t`; called once per outgoing synapse
+    /// `pre -> post` of edge `edge` before the spike is added to the traces (LTD for
+    /// pre-after-post). Weight is i32 Q16.16; implementation applies clamping.
+    fn apply_pre(&mut self, edge: u32, pre: NeuronId, post: NeuronId, t: u64, weight: &mut i32);
+
+    /// Post side of a pairing: `post` spiked at `t`; called once per incoming synapse
+    /// `pre -> post` of edge `edge` before the spike is added to the traces (LTP for
+    /// post-after-pre). Weight is i32 Q16.16; implementation applies clamping.
+    fn apply_post(&mut self, edge: u32, pre: NeuronId, post: NeuronId, t: u64, weight: &mut i32);
+
+    /// Apply both sides of the update to the weight of edge `edge` for its synapse
+    /// `pre -> post` using the current traces (convenience for callers without spike times).
+    fn apply_edge(&mut self, edge: u32, pre: NeuronId, post: NeuronId, weight: &mut i32) {
+        self.apply_post(edge, pre, post, 0, weight);
+        self.apply_pre(edge, pre, post, 0, weight);
+    }
+
+    /// Called when a reward/neuromodulator signal (Q16.16) is delivered.
+    /// Rules that are not reward-gated ignore it.
+    fn on_reward(&mut self, _reward: i32) {}
+
+    /// Commit the reward-gated update for edge `edge` after `on_reward`.
+    /// Called once per edge; default is a no-op.
+    fn consolidate_edge(&mut self, _edge: u32, _weight: &mut i32) {}
+
+    /// Follow `SnnRuntimePlus::remove_edges`: drop per-edge state of the `removed` ids
+    /// (ascending) and shift later ids down. Default is a no-op (no per-edge state).
+    fn on_edges_removed(&mut self, _removed: &[u32]) {}
 
-    /// Apply update to an edge weight that connects `pre -> post`.
-    /// Weight is i32 Q16.16; implementation applies clamping.
-    fn apply_edge(&mut self, pre: u32, post: u32, weight: &mut i32);
+    /// Forget all spike history (traces, eligibility, pending reward), keeping parameters.
+    /// Used by `SnnRuntimePlus::reset_state`; the default keeps the state.
+    fn reset(&mut self) {}
+
+    /// Independent copy for `SnnRuntimePlus::fork`; None (the default) if the rule cannot
+    /// be copied, which makes the fork fail.
+    fn fork(&self) -> Option<Box<dyn PlasticityRule>> {
+        None
+    }
 }
 
 /// Minimal quantized STDP rule:
 /// - Pre and post traces (Q16.16) with exponential-like decay via multiply by alpha in (0,1)
 /// - On updates, weight = clamp(w - a_minus * post_trace + a_plus * pre_trace)
+#[derive(Clone)]
 pub struct QuantizedStdp {
     // Parameters (Q16.16)
     a_plus: i32,
@@ -93,7 +173,7 @@ impl QuantizedStdp {
         Self::new(0.01, 0.012, 0.96, 0.96, 0.0, 1.0)
     }
 
-    fn ensure_neuron(&mut self, id: u32) {
+    fn ensure_neuron(&mut self, id: NeuronId) {
         let need = id as usize + 1;
         if self.pre_trace.len() < need {
             self.pre_trace.resize(need, 0);
@@ -104,7 +184,7 @@ impl QuantizedStdp {
     }
 
     /// Inspect traces (for tests/diagnostics)
-    pub fn traces(&self, id: u32) -> (i32, i32) {
+    pub fn traces(&self, id: NeuronId) -> (i32, i32) {
         let idx = id as usize;
         let pre = *self.pre_trace.get(idx).unwrap_or(&0);
         let post = *self.post_trace.get(idx).unwrap_or(&0);
@@ -123,39 +203,486 @@ impl PlasticityRule for QuantizedStdp {
         }
     }
 
-    fn on_pre_spike(&mut self, pre: u32, _t: u64) {
+    fn decay_ticks(&mut self, ticks: u64) {
+        let (f_pre, f_post) = (fixed_pow(self.alpha_pre, ticks), fixed_pow(self.alpha_post, ticks));
+        for tr in &mut self.pre_trace {
+            *tr = fx_mul(*tr, f_pre);
+        }
+        for tr in &mut self.post_trace {
+            *tr = fx_mul(*tr, f_post);
+        }
+    }
+
+    fn on_pre_spike(&mut self, pre: NeuronId, _t: u64) {
         self.ensure_neuron(pre);
         // Increment pre trace by 1.0 on spike
         self.pre_trace[pre as usize] = fx_add_sat(self.pre_trace[pre as usize], ONE);
     }
 
-    fn on_post_spike(&mut self, post: u32, _t: u64) {
+    fn on_post_spike(&mut self, post: NeuronId, _t: u64) {
         self.ensure_neuron(post);
         // Increment post trace by 1.0 on spike
         self.post_trace[post as usize] = fx_add_sat(self.post_trace[post as usize], ONE);
     }
 
-    fn apply_edge(&mut self, pre: u32, post: u32, weight: &mut i32) {
+    fn apply_pre(&mut self, _edge: u32, _pre: NeuronId, post: NeuronId, _t: u64, weight: &mut i32) {
+        self.ensure_neuron(post);
+        // Δw = -a_minus*post_tr
+        let ltd = fx_mul(self.a_minus, self.post_trace[post as usize]);
+        *weight = fx_clamp(fx_sub_sat(*weight, ltd), self.w_min, self.w_max);
+    }
+
+    fn apply_post(&mut self, _edge: u32, pre: NeuronId, _post: NeuronId, _t: u64, weight: &mut i32) {
         self.ensure_neuron(pre);
+        // Δw = +a_plus*pre_tr
+        let ltp = fx_mul(self.a_plus, self.pre_trace[pre as usize]);
+        *weight = fx_clamp(fx_add_sat(*weight, ltp), self.w_min, self.w_max);
+    }
+
+    fn reset(&mut self) {
+        self.pre_trace.clear();
+        self.post_trace.clear();
+    }
+
+    fn fork(&self) -> Option<Box<dyn PlasticityRule>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Reward-modulated STDP (R-STDP):
+/// - Pre/post traces as in QuantizedStdp
+/// - STDP updates are accumulated into a per-edge eligibility trace instead of the weight;
+///   all synapses of a hyperedge share its trace
+/// - Eligibility decays every tick by alpha_elig and is clamped to [-e_max, e_max]
+/// - On reward r: weight = clamp(w + r * eligibility)
+#[derive(Clone)]
+pub struct RewardModulatedStdp {
+    // Parameters (Q16.16)
+    a_plus: i32,
+    a_minus: i32,
+    alpha_pre: i32,
+    alpha_post: i32,
+    alpha_elig: i32,
+    e_max: i32,
+    w_min: i32,
+    w_max: i32,
+
+    // State traces per neuron (Q16.16)
+    pre_trace: Vec<i32>,
+    post_trace: Vec<i32>,
+    // Eligibility per edge, indexed by edge id (Q16.16); grown on first use
+    eligibility: Vec<i32>,
+    // Reward pending consolidation (Q16.16)
+    reward: i32,
+}
+
+impl RewardModulatedStdp {
+    /// Create with floating parameters (converted to Q16.16).
+    /// `alpha_elig` is the per-tick eligibility decay factor and `e_max` bounds its magnitude.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        a_plus: f32,
+        a_minus: f32,
+        alpha_pre: f32,
+        alpha_post: f32,
+        alpha_elig: f32,
+        e_max: f32,
+        w_min: f32,
+        w_max: f32,
+    ) -> Self {
+        Self {
+            a_plus: fx_from_f32(a_plus),
+            a_minus: fx_from_f32(a_minus),
+            alpha_pre: fx_from_f32(alpha_pre),
+            alpha_post: fx_from_f32(alpha_post),
+            alpha_elig: fx_from_f32(alpha_elig),
+            e_max: fx_from_f32(e_max),
+            w_min: fx_from_f32(w_min),
+            w_max: fx_from_f32(w_max),
+            pre_trace: Vec::new(),
+            post_trace: Vec::new(),
+            eligibility: Vec::new(),
+            reward: 0,
+        }
+    }
+
+    /// Defaults matching QuantizedStdp, with a slower eligibility decay (~0.99 per tick)
+    pub fn with_defaults() -> Self {
+        Self::new(0.01, 0.012, 0.96, 0.96, 0.99, 1.0, 0.0, 1.0)
+    }
+
+    fn ensure_neuron(&mut self, id: NeuronId) {
+        let need = id as usize + 1;
+        if self.pre_trace.len() < need {
+            self.pre_trace.resize(need, 0);
+        }
+        if self.post_trace.len() < need {
+            self.post_trace.resize(need, 0);
+        }
+    }
+
+    /// Inspect traces (for tests/diagnostics)
+    pub fn traces(&self, id: NeuronId) -> (i32, i32) {
+        let idx = id as usize;
+        let pre = *self.pre_trace.get(idx).unwrap_or(&0);
+        let post = *self.post_trace.get(idx).unwrap_or(&0);
+        (pre, post)
+    }
+
+    /// Inspect the eligibility trace of edge `edge` (Q16.16)
+    pub fn eligibility(&self, edge: u32) -> i32 {
+        *self.eligibility.get(edge as usize).unwrap_or(&0)
+    }
+
+    fn eligibility_slot(&mut self, edge: u32) -> &mut i32 {
+        let idx = edge as usize;
+        if self.eligibility.len() <= idx {
+            self.eligibility.resize(idx + 1, 0);
+        }
+        &mut self.eligibility[idx]
+    }
+}
+
+impl PlasticityRule for RewardModulatedStdp {
+    fn decay(&mut self) {
+        for tr in &mut self.pre_trace {
+            *tr = fx_mul(*tr, self.alpha_pre);
+        }
+        for tr in &mut self.post_trace {
+            *tr = fx_mul(*tr, self.alpha_post);
+        }
+        for e in &mut self.eligibility {
+            *e = fx_decay(*e, self.alpha_elig);
+        }
+    }
+
+    fn decay_ticks(&mut self, ticks: u64) {
+        let (f_pre, f_post) = (fixed_pow(self.alpha_pre, ticks), fixed_pow(self.alpha_post, ticks));
+        let f_elig = fixed_pow(self.alpha_elig, ticks);
+        for tr in &mut self.pre_trace {
+            *tr = fx_mul(*tr, f_pre);
+        }
+        for tr in &mut self.post_trace {
+            *tr = fx_mul(*tr, f_post);
+        }
+        for e in &mut self.eligibility {
+            *e = fx_decay(*e, f_elig);
+        }
+    }
+
+    fn on_pre_spike(&mut self, pre: NeuronId, _t: u64) {
+        self.ensure_neuron(pre);
+        self.pre_trace[pre as usize] = fx_add_sat(self.pre_trace[pre as usize], ONE);
+    }
+
+    fn on_post_spike(&mut self, post: NeuronId, _t: u64) {
         self.ensure_neuron(post);
+        self.post_trace[post as usize] = fx_add_sat(self.post_trace[post as usize], ONE);
+    }
 
-        let pre_tr = self.pre_trace[pre as usize];
-        let post_tr = self.post_trace[post as usize];
+    // Same Δ as QuantizedStdp, but tagged onto the edge instead of the weight
+    fn apply_pre(&mut self, edge: u32, _pre: NeuronId, post: NeuronId, _t: u64, _weight: &mut i32) {
+        self.ensure_neuron(post);
+        let ltd = fx_mul(self.a_minus, self.post_trace[post as usize]);
+        let e_max = self.e_max;
+        let e = self.eligibility_slot(edge);
+        *e = fx_clamp(fx_sub_sat(*e, ltd), -e_max, e_max);
+    }
+
+    fn apply_post(&mut self, edge: u32, pre: NeuronId, _post: NeuronId, _t: u64, _weight: &mut i32) {
+        self.ensure_neuron(pre);
+        let ltp = fx_mul(self.a_plus, self.pre_trace[pre as usize]);
+        let e_max = self.e_max;
+        let e = self.eligibility_slot(edge);
+        *e = fx_clamp(fx_add_sat(*e, ltp), -e_max, e_max);
+    }
 
-        // Δw = +a_plus*pre_tr - a_minus*post_tr
-        let ltp = fx_mul(self.a_plus, pre_tr);
-        let ltd = fx_mul(self.a_minus, post_tr);
-        let mut new_w = fx_add_sat(*weight, ltp);
-        new_w = fx_sub_sat(new_w, ltd);
+    fn on_reward(&mut self, reward: i32) {
+        self.reward = reward;
+    }
 
-        // Clamp
-        if new_w < self.w_min {
-            new_w = self.w_min;
+    fn consolidate_edge(&mut self, edge: u32, weight: &mut i32) {
+        let e = self.eligibility(edge);
+        if e == 0 || self.reward == 0 {
+            return;
         }
-        if new_w > self.w_max {
-            new_w = self.w_max;
+        let dw = fx_mul(self.reward, e);
+        *weight = fx_clamp(fx_add_sat(*weight, dw), self.w_min, self.w_max);
+    }
+
+    fn reset(&mut self) {
+        self.pre_trace.clear();
+        self.post_trace.clear();
+        self.eligibility.clear();
+        self.reward = 0;
+    }
+
+    fn on_edges_removed(&mut self, removed: &[u32]) {
+        let mut idx = 0u32;
+        self.eligibility.retain(|_| {
+            idx += 1;
+            removed.binary_search(&(idx - 1)).is_err()
+        });
+    }
+
+    fn fork(&self) -> Option<Box<dyn PlasticityRule>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Exponential trace updated only at events: value is valid at `last` and decays
+/// by `alpha^(t - last)` when read at a later time `t`.
+#[derive(Clone, Copy, Debug, Default)]
+struct EventTrace {
+    value: i32,
+    last: u64,
+}
+
+impl EventTrace {
+    #[inline]
+    fn at(&self, t: u64, alpha: i32) -> i32 {
+        if self.value == 0 {
+            return 0;
+        }
+        fx_mul(self.value, fixed_pow(alpha, t.saturating_sub(self.last)))
+    }
+
+    /// Register a spike at `t`: add 1.0 (all-to-all) or reset to 1.0 (nearest-neighbour)
+    #[inline]
+    fn bump(&mut self, t: u64, alpha: i32, pairing: StdpPairing) {
+        self.value = match pairing {
+            StdpPairing::AllToAll => fx_add_sat(self.at(t, alpha), ONE),
+            StdpPairing::NearestNeighbour => ONE,
+        };
+        self.last = t;
+    }
+}
+
+#[inline]
+fn trace_slot(traces: &mut Vec<EventTrace>, id: NeuronId) -> &mut EventTrace {
+    let need = id as usize + 1;
+    if traces.len() < need {
+        traces.resize(need, EventTrace::default());
+    }
+    &mut traces[id as usize]
+}
+
+#[inline]
+fn trace_at(traces: &[EventTrace], id: NeuronId, t: u64, alpha: i32) -> i32 {
+    traces.get(id as usize).map(|tr| tr.at(t, alpha)).unwrap_or(0)
+}
+
+/// Which earlier spikes a new spike is paired with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StdpPairing {
+    /// Only the most recent spike of the other neuron counts
+    NearestNeighbour,
+    /// Every earlier spike of the other neuron counts (traces accumulate)
+    AllToAll,
+}
+
+/// Pair-based STDP with event-timed traces:
+/// - post spike at t_post: Δw = +a_plus * exp(-(t_post - t_pre) / tau_plus)
+/// - pre spike at t_pre:   Δw = -a_minus * exp(-(t_pre - t_post) / tau_minus)
+///
+/// Spike times are emission times (synaptic delay is not part of Δt) and pairs within
+/// the same tick are ignored. `decay()` is a no-op; traces decay in closed form on read.
+#[derive(Clone)]
+pub struct PairStdp {
+    // Parameters (Q16.16; alphas are per-tick decay factors)
+    a_plus: i32,
+    a_minus: i32,
+    alpha_plus: i32,
+    alpha_minus: i32,
+    w_min: i32,
+    w_max: i32,
+    pairing: StdpPairing,
+
+    // Event-timed traces per neuron
+    pre_trace: Vec<EventTrace>,
+    post_trace: Vec<EventTrace>,
+}
+
+impl PairStdp {
+    /// Create with floating parameters; `tau_plus`/`tau_minus` are in ticks.
+    pub fn new(
+        pairing: StdpPairing,
+        a_plus: f32,
+        a_minus: f32,
+        tau_plus: f32,
+        tau_minus: f32,
+        w_min: f32,
+        w_max: f32,
+    ) -> Self {
+        Self {
+            a_plus: fx_from_f32(a_plus),
+            a_minus: fx_from_f32(a_minus),
+            alpha_plus: fx_alpha_from_tau(tau_plus),
+            alpha_minus: fx_alpha_from_tau(tau_minus),
+            w_min: fx_from_f32(w_min),
+            w_max: fx_from_f32(w_max),
+            pairing,
+            pre_trace: Vec::new(),
+            post_trace: Vec::new(),
+        }
+    }
+
+    /// Nearest-neighbour pairing with a_plus = 0.01, a_minus = 0.012, tau = 20 ticks, w in [0, 1]
+    pub fn nearest_with_defaults() -> Self {
+        Self::new(StdpPairing::NearestNeighbour, 0.01, 0.012, 20.0, 20.0, 0.0, 1.0)
+    }
+
+    /// All-to-all pairing with the same defaults as `nearest_with_defaults`
+    pub fn all_to_all_with_defaults() -> Self {
+        Self::new(StdpPairing::AllToAll, 0.01, 0.012, 20.0, 20.0, 0.0, 1.0)
+    }
+
+    /// Inspect (pre, post) traces of a neuron at time `t` (for tests/diagnostics)
+    pub fn traces(&self, id: NeuronId, t: u64) -> (i32, i32) {
+        (
+            trace_at(&self.pre_trace, id, t, self.alpha_plus),
+            trace_at(&self.post_trace, id, t, self.alpha_minus),
+        )
+    }
+}
+
+impl PlasticityRule for PairStdp {
+    fn decay(&mut self) {}
+
+    fn on_pre_spike(&mut self, pre: NeuronId, t: u64) {
+        let (alpha, pairing) = (self.alpha_plus, self.pairing);
+        trace_slot(&mut self.pre_trace, pre).bump(t, alpha, pairing);
+    }
+
+    fn on_post_spike(&mut self, post: NeuronId, t: u64) {
+        let (alpha, pairing) = (self.alpha_minus, self.pairing);
+        trace_slot(&mut self.post_trace, post).bump(t, alpha, pairing);
+    }
+
+    fn apply_pre(&mut self, _edge: u32, _pre: NeuronId, post: NeuronId, t: u64, weight: &mut i32) {
+        let ltd = fx_mul(self.a_minus, trace_at(&self.post_trace, post, t, self.alpha_minus));
+        *weight = fx_clamp(fx_sub_sat(*weight, ltd), self.w_min, self.w_max);
+    }
+
+    fn apply_post(&mut self, _edge: u32, pre: NeuronId, _post: NeuronId, t: u64, weight: &mut i32) {
+        let ltp = fx_mul(self.a_plus, trace_at(&self.pre_trace, pre, t, self.alpha_plus));
+        *weight = fx_clamp(fx_add_sat(*weight, ltp), self.w_min, self.w_max);
+    }
+
+    fn reset(&mut self) {
+        self.pre_trace.clear();
+        self.post_trace.clear();
+    }
+
+    fn fork(&self) -> Option<Box<dyn PlasticityRule>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// Triplet STDP (Pfister & Gerstner 2006, all-to-all) with event-timed traces.
+/// Pre traces r1 (tau_plus), r2 (tau_x); post traces o1 (tau_minus), o2 (tau_y):
+/// - post spike: Δw = +r1 * (a2_plus + a3_plus * o2)
+/// - pre spike:  Δw = -o1 * (a2_minus + a3_minus * r2)
+///
+/// o2 and r2 are read before the current spike is added, as in the original model.
+#[derive(Clone)]
+pub struct TripletStdp {
+    // Parameters (Q16.16; alphas are per-tick decay factors)
+    a2_plus: i32,
+    a3_plus: i32,
+    a2_minus: i32,
+    a3_minus: i32,
+    alpha_plus: i32,
+    alpha_minus: i32,
+    alpha_x: i32,
+    alpha_y: i32,
+    w_min: i32,
+    w_max: i32,
+
+    // Event-timed traces per neuron
+    r1: Vec<EventTrace>,
+    r2: Vec<EventTrace>,
+    o1: Vec<EventTrace>,
+    o2: Vec<EventTrace>,
+}
+
+impl TripletStdp {
+    /// Create with floating parameters; time constants are in ticks.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        a2_plus: f32,
+        a3_plus: f32,
+        a2_minus: f32,
+        a3_minus: f32,
+        tau_plus: f32,
+        tau_minus: f32,
+        tau_x: f32,
+        tau_y: f32,
+        w_min: f32,
+        w_max: f32,
+    ) -> Self {
+        Self {
+            a2_plus: fx_from_f32(a2_plus),
+            a3_plus: fx_from_f32(a3_plus),
+            a2_minus: fx_from_f32(a2_minus),
+            a3_minus: fx_from_f32(a3_minus),
+            alpha_plus: fx_alpha_from_tau(tau_plus),
+            alpha_minus: fx_alpha_from_tau(tau_minus),
+            alpha_x: fx_alpha_from_tau(tau_x),
+            alpha_y: fx_alpha_from_tau(tau_y),
+            w_min: fx_from_f32(w_min),
+            w_max: fx_from_f32(w_max),
+            r1: Vec::new(),
+            r2: Vec::new(),
+            o1: Vec::new(),
+            o2: Vec::new(),
         }
-        *weight = new_w;
+    }
+
+    /// Visual cortex all-to-all fit from Pfister & Gerstner (2006), with 1 tick = 1 ms
+    pub fn with_defaults() -> Self {
+        Self::new(5e-10, 6.2e-3, 7e-3, 2.3e-4, 16.8, 33.7, 101.0, 125.0, 0.0, 1.0)
+    }
+}
+
+impl PlasticityRule for TripletStdp {
+    fn decay(&mut self) {}
+
+    fn on_pre_spike(&mut self, pre: NeuronId, t: u64) {
+        let (ap, ax) = (self.alpha_plus, self.alpha_x);
+        trace_slot(&mut self.r1, pre).bump(t, ap, StdpPairing::AllToAll);
+        trace_slot(&mut self.r2, pre).bump(t, ax, StdpPairing::AllToAll);
+    }
+
+    fn on_post_spike(&mut self, post: NeuronId, t: u64) {
+        let (am, ay) = (self.alpha_minus, self.alpha_y);
+        trace_slot(&mut self.o1, post).bump(t, am, StdpPairing::AllToAll);
+        trace_slot(&mut self.o2, post).bump(t, ay, StdpPairing::AllToAll);
+    }
+
+    fn apply_pre(&mut self, _edge: u32, pre: NeuronId, post: NeuronId, t: u64, weight: &mut i32) {
+        let o1 = trace_at(&self.o1, post, t, self.alpha_minus);
+        let r2 = trace_at(&self.r2, pre, t, self.alpha_x);
+        let ltd = fx_mul(o1, fx_add_sat(self.a2_minus, fx_mul(self.a3_minus, r2)));
+        *weight = fx_clamp(fx_sub_sat(*weight, ltd), self.w_min, self.w_max);
+    }
+
+    fn apply_post(&mut self, _edge: u32, pre: NeuronId, post: NeuronId, t: u64, weight: &mut i32) {
+        let r1 = trace_at(&self.r1, pre, t, self.alpha_plus);
+        let o2 = trace_at(&self.o2, post, t, self.alpha_y);
+        let ltp = fx_mul(r1, fx_add_sat(self.a2_plus, fx_mul(self.a3_plus, o2)));
+        *weight = fx_clamp(fx_add_sat(*weight, ltp), self.w_min, self.w_max);
+    }
+
+    fn reset(&mut self) {
+        self.r1.clear();
+        self.r2.clear();
+        self.o1.clear();
+        self.o2.clear();
+    }
+
+    fn fork(&self) -> Option<Box<dyn PlasticityRule>> {
+        Some(Box::new(self.clone()))
     }
 }
 
@@ -173,7 +700,7 @@ mod tests {
         stdp.decay();
         stdp.on_post_spike(2, 1);
 
-        stdp.apply_edge(1, 2, &mut w);
+        stdp.apply_edge(0, 1, 2, &mut w);
         // Expect weight within [0,1]
         assert!(w >= fx_from_f32(0.0) && w <= fx_from_f32(1.0));
     }
@@ -198,8 +725,191 @@ mod tests {
         let mut stdp = QuantizedStdp::new(1.0, 0.0, 1.0, 1.0, 0.25, 0.75);
         let mut w = fx_from_f32(0.7);
         stdp.on_pre_spike(0, 0);
-        stdp.apply_edge(0, 1, &mut w);
+        stdp.apply_edge(0, 0, 1, &mut w);
         // Should clamp to 0.75 max
         assert!((fx_to_f32(w) - 0.75).abs() < 1e-3);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_rstdp_no_weight_change_without_reward() {
+        let mut rule = RewardModulatedStdp::with_defaults();
+        let mut w = fx_from_f32(0.5);
+        rule.on_pre_spike(0, 0);
+        rule.on_post_spike(1, 0);
+        rule.apply_edge(0, 0, 1, &mut w);
+        assert_eq!(w, fx_from_f32(0.5));
+        assert!(rule.eligibility(0) != 0);
+    }
+
+    #[test]
+    fn test_rstdp_reward_sign_sets_direction() {
+        let mut rule = RewardModulatedStdp::new(0.1, 0.0, 0.96, 0.96, 0.99, 1.0, 0.0, 1.0);
+        rule.on_pre_spike(0, 0);
+        let mut w = fx_from_f32(0.5);
+        rule.apply_edge(0, 0, 1, &mut w);
+
+        let mut up = w;
+        rule.on_reward(ONE);
+        rule.consolidate_edge(0, &mut up);
+        assert!(up > w);
+
+        let mut down = w;
+        rule.on_reward(-ONE);
+        rule.consolidate_edge(0, &mut down);
+        assert!(down < w);
+    }
+
+    #[test]
+    fn test_rstdp_eligibility_decays_and_clamps() {
+        let mut rule = RewardModulatedStdp::new(1.0, 0.0, 1.0, 1.0, 0.5, 0.25, 0.0, 1.0);
+        rule.on_pre_spike(0, 0);
+        let mut w = 0;
+        rule.apply_edge(0, 0, 1, &mut w);
+        assert_eq!(rule.eligibility(0), fx_from_f32(0.25));
+
+        rule.decay();
+        assert_eq!(rule.eligibility(0), fx_from_f32(0.125));
+        for _ in 0..32 {
+            rule.decay();
+        }
+        assert_eq!(rule.eligibility(0), 0);
+    }
+
+    #[test]
+    fn test_rstdp_eligibility_is_per_edge_and_follows_removal() {
+        let mut rule = RewardModulatedStdp::new(0.5, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 1.0);
+        rule.on_pre_spike(0, 0);
+        let mut w = 0;
+        // Parallel edges 0 and 2 over the same pair keep separate traces
+        rule.apply_post(0, 0, 1, 1, &mut w);
+        rule.apply_post(2, 0, 1, 1, &mut w);
+        rule.apply_post(2, 0, 1, 2, &mut w);
+        assert_eq!(rule.eligibility(0), fx_from_f32(0.5));
+        assert_eq!(rule.eligibility(1), 0);
+        assert_eq!(rule.eligibility(2), ONE);
+
+        // Removing edge 0 shifts edge 2 down to id 1
+        rule.on_edges_removed(&[0]);
+        assert_eq!(rule.eligibility(0), 0);
+        assert_eq!(rule.eligibility(1), ONE);
+    }
+
+    #[test]
+    fn test_decay_ticks_is_within_the_rounding_bound_of_per_tick_decay() {
+        let (a_pre, a_post, a_elig) = (0.9, 0.8, 0.99);
+        let make = || {
+            let mut rule = RewardModulatedStdp::new(1.0, 0.5, a_pre, a_post, a_elig, 1.0, -1.0, 1.0);
+            rule.on_pre_spike(0, 0);
+            rule.on_post_spike(1, 0);
+            let mut w = 0;
+            rule.apply_edge(0, 0, 1, &mut w);
+            rule
+        };
+        for ticks in [1, 7, 50, 1_000, 10_000] {
+            let (mut fast, mut slow) = (make(), make());
+            fast.decay_ticks(ticks);
+            for _ in 0..ticks {
+                slow.decay();
+            }
+            // Bound from `PlasticityRule::decay_ticks`, in units of 2^-16
+            let close = |x: i32, y: i32, alpha: f32| {
+                let bound = 1.0 / (1.0 - alpha as f64) + 2.0 * (ticks as f64).log2() + 2.0;
+                ((x - y).abs() as f64) < bound
+            };
+            assert!(close(fast.traces(0).0, slow.traces(0).0, a_pre), "pre trace after {ticks}");
+            assert!(close(fast.traces(1).1, slow.traces(1).1, a_post), "post trace after {ticks}");
+            assert!(close(fast.eligibility(0), slow.eligibility(0), a_elig), "eligibility after {ticks}");
+            if ticks == 1 {
+                assert_eq!((fast.traces(0), fast.eligibility(0)), (slow.traces(0), slow.eligibility(0)));
+            }
+        }
+    }
+
+    /// Drive one synapse 0 -> 1 through a spike train in the runtime's order:
+    /// pair updates for all spikes of a tick first, then trace increments.
+    fn run_pairing<R: PlasticityRule>(rule: &mut R, pre: &[u64], post: &[u64]) -> i32 {
+        let mut w = 0;
+        let mut times: Vec<u64> = pre.iter().chain(post.iter()).copied().collect();
+        times.sort_unstable();
+        times.dedup();
+        for t in times {
+            let pre_now = pre.contains(&t);
+            let post_now = post.contains(&t);
+            if post_now {
+                rule.apply_post(0, 0, 1, t, &mut w);
+            }
+            if pre_now {
+                rule.apply_pre(0, 0, 1, t, &mut w);
+            }
+            if pre_now {
+                rule.on_pre_spike(0, t);
+            }
+            if post_now {
+                rule.on_post_spike(1, t);
+            }
+        }
+        w
+    }
+
+    fn wide_pair(pairing: StdpPairing) -> PairStdp {
+        PairStdp::new(pairing, 1.0, 0.5, 20.0, 10.0, -8.0, 8.0)
+    }
+
+    #[test]
+    fn test_pair_stdp_matches_exponential_window() {
+        for dt in 1..=60u64 {
+            let ltp = fx_to_f32(run_pairing(&mut wide_pair(StdpPairing::AllToAll), &[100], &[100 + dt]));
+            let expect = (-(dt as f32) / 20.0).exp();
+            assert!((ltp - expect).abs() < 2e-3, "LTP dt={} got {} want {}", dt, ltp, expect);
+
+            let ltd = fx_to_f32(run_pairing(&mut wide_pair(StdpPairing::AllToAll), &[100 + dt], &[100]));
+            let expect = -0.5 * (-(dt as f32) / 10.0).exp();
+            assert!((ltd - expect).abs() < 2e-3, "LTD dt={} got {} want {}", dt, ltd, expect);
+        }
+    }
+
+    #[test]
+    fn test_pair_stdp_same_tick_is_ignored() {
+        assert_eq!(run_pairing(&mut wide_pair(StdpPairing::AllToAll), &[5], &[5]), 0);
+    }
+
+    #[test]
+    fn test_nearest_vs_all_to_all_pairing() {
+        // Two pre spikes before one post spike
+        let pre = [0, 10];
+        let post = [15];
+        let nn = fx_to_f32(run_pairing(&mut wide_pair(StdpPairing::NearestNeighbour), &pre, &post));
+        let all = fx_to_f32(run_pairing(&mut wide_pair(StdpPairing::AllToAll), &pre, &post));
+        let e5 = (-5.0f32 / 20.0).exp();
+        let e15 = (-15.0f32 / 20.0).exp();
+        assert!((nn - e5).abs() < 2e-3);
+        assert!((all - (e5 + e15)).abs() < 2e-3);
+    }
+
+    #[test]
+    fn test_pair_stdp_clamps() {
+        let mut rule = PairStdp::new(StdpPairing::AllToAll, 1.0, 1.0, 20.0, 20.0, -0.1, 0.1);
+        let w = run_pairing(&mut rule, &[0], &[1]);
+        assert_eq!(w, fx_from_f32(0.1));
+    }
+
+    #[test]
+    fn test_triplet_post_pre_post() {
+        let (a2p, a3p, a2m, a3m) = (0.5, 0.25, 0.5, 0.125);
+        let (tp, tm, tx, ty) = (16.0f32, 32.0f32, 100.0f32, 120.0f32);
+        let mut rule = TripletStdp::new(a2p, a3p, a2m, a3m, tp, tm, tx, ty, -8.0, 8.0);
+
+        // post at 0, pre at 5, post at 10
+        let w = fx_to_f32(run_pairing(&mut rule, &[5], &[0, 10]));
+        let ltd = (-5.0 / tm).exp() * a2m;
+        let ltp = (-5.0 / tp).exp() * (a2p + a3p * (-10.0 / ty).exp());
+        assert!((w - (ltp - ltd)).abs() < 2e-3, "got {} want {}", w, ltp - ltd);
+    }
+
+    #[test]
+    fn test_triplet_reduces_to_pair_without_triplet_terms() {
+        let mut rule = TripletStdp::new(1.0, 0.0, 0.5, 0.0, 20.0, 10.0, 100.0, 100.0, -8.0, 8.0);
+        let w = fx_to_f32(run_pairing(&mut rule, &[0], &[7]));
+        assert!((w - (-7.0f32 / 20.0).exp()).abs() < 2e-3);
+    }
+}
@@ -0,0 +1,413 @@
+//! Composable plasticity rule stack for snn-core-plus (feature "plasticity").
+//!
+//! - Named entries hold either a synaptic PlasticityRule or a HomeostaticRule.
+//! - Each synaptic entry has a RuleScope (all edges, listed edges, or source/target populations).
+//! - Entries can be enabled/disabled at runtime; a disabled entry is paused entirely
+//!   (no trace decay or spike bookkeeping) and resumes where it left off.
+//! - Freezing keeps trace bookkeeping running but suppresses every weight/threshold change,
+//!   for evaluation phases.
+//! - Entries are looked up by name; `rule_by_name` builds default rules from config strings.
+
+use snn_core::NeuronId;
+
+use crate::homeostasis::HomeostaticRule;
+use crate::runtime_plus::ForkError;
+use crate::plasticity::{PairStdp, PlasticityRule, QuantizedStdp, RewardModulatedStdp, TripletStdp};
+
+/// Sorted, deduplicated id list (built with `IdSet::from`), so lookups can binary-search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdSet<T>(Vec<T>);
+
+impl<T: Ord> From<Vec<T>> for IdSet<T> {
+    fn from(mut ids: Vec<T>) -> Self {
+        ids.sort_unstable();
+        ids.dedup();
+        IdSet(ids)
+    }
+}
+
+impl<T: Ord> IdSet<T> {
+    #[inline]
+    pub fn contains(&self, id: &T) -> bool {
+        self.0.binary_search(id).is_ok()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+}
+
+/// Which synapses a rule applies to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuleScope {
+    /// Every edge
+    All,
+    /// Only the listed edge ids; `SnnRuntimePlus::remove_edges` (and so structural pruning)
+    /// renumbers them along with the edges
+    Edges(IdSet<u32>),
+    /// Synapses whose pre-synaptic neuron is in the population
+    Sources(IdSet<NeuronId>),
+    /// Synapses whose post-synaptic neuron is in the population
+    Targets(IdSet<NeuronId>),
+}
+
+impl RuleScope {
+    pub fn edges(ids: Vec<u32>) -> Self {
+        RuleScope::Edges(ids.into())
+    }
+
+    pub fn sources(ids: Vec<NeuronId>) -> Self {
+        RuleScope::Sources(ids.into())
+    }
+
+    pub fn targets(ids: Vec<NeuronId>) -> Self {
+        RuleScope::Targets(ids.into())
+    }
+
+    #[inline]
+    pub fn matches(&self, edge: u32, pre: NeuronId, post: NeuronId) -> bool {
+        match self {
+            RuleScope::All => true,
+            RuleScope::Edges(ids) => ids.contains(&edge),
+            RuleScope::Sources(ids) => ids.contains(&pre),
+            RuleScope::Targets(ids) => ids.contains(&post),
+        }
+    }
+
+    /// Follow an edge removal: drop the `removed` ids (ascending) and shift the later ones
+    /// down, as `EdgeArena::remove` renumbers them.
+    pub fn remap_removed_edges(&mut self, removed: &[u32]) {
+        if let RuleScope::Edges(IdSet(ids)) = self {
+            ids.retain(|id| removed.binary_search(id).is_err());
+            for id in ids.iter_mut() {
+                *id -= removed.partition_point(|&r| r < *id) as u32;
+            }
+        }
+    }
+}
+
+/// A rule held by the stack.
+pub enum StackRule {
+    Synaptic(Box<dyn PlasticityRule>),
+    Homeostatic(Box<dyn HomeostaticRule>),
+}
+
+/// One named entry of the stack.
+pub struct StackEntry {
+    pub name: String,
+    pub rule: StackRule,
+    /// Scope of synaptic rules (homeostatic rules always act on the whole network)
+    pub scope: RuleScope,
+    pub enabled: bool,
+}
+
+/// Ordered stack of plasticity rules; rules run in insertion order.
+#[derive(Default)]
+pub struct PlasticityStack {
+    entries: Vec<StackEntry>,
+    frozen: bool,
+}
+
+impl PlasticityStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert an entry; an existing entry with the same name is replaced in place.
+    pub fn push(&mut self, name: &str, rule: StackRule, scope: RuleScope) {
+        let entry = StackEntry { name: name.to_string(), rule, scope, enabled: true };
+        match self.entries.iter_mut().find(|e| e.name == name) {
+            Some(slot) => *slot = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// Insert a synaptic rule under `name` with the given scope.
+    pub fn push_rule<R: PlasticityRule + 'static>(&mut self, name: &str, rule: R, scope: RuleScope) {
+        self.push(name, StackRule::Synaptic(Box::new(rule)), scope);
+    }
+
+    /// Insert a homeostatic rule under `name`.
+    pub fn push_homeostasis<H: HomeostaticRule + 'static>(&mut self, name: &str, rule: H) {
+        self.push(name, StackRule::Homeostatic(Box::new(rule)), RuleScope::All);
+    }
+
+    /// Remove and return the entry called `name`.
+    pub fn remove(&mut self, name: &str) -> Option<StackEntry> {
+        let idx = self.entries.iter().position(|e| e.name == name)?;
+        Some(self.entries.remove(idx))
+    }
+
+    /// Remove all synaptic rules, keeping homeostatic ones.
+    pub fn clear_synaptic(&mut self) {
+        self.entries.retain(|e| matches!(e.rule, StackRule::Homeostatic(_)));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn get(&self, name: &str) -> Option<&StackEntry> {
+        self.entries.iter().find(|e| e.name == name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut StackEntry> {
+        self.entries.iter_mut().find(|e| e.name == name)
+    }
+
+    /// Entry names in execution order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|e| e.name.as_str())
+    }
+
+    /// Enable or disable the entry called `name`; returns false if there is none.
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        match self.get_mut(name) {
+            Some(e) => {
+                e.enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn set_all_enabled(&mut self, enabled: bool) {
+        self.entries.iter_mut().for_each(|e| e.enabled = enabled);
+    }
+
+    /// True if at least one entry is enabled.
+    pub fn is_active(&self) -> bool {
+        self.entries.iter().any(|e| e.enabled)
+    }
+
+    /// Freeze (or unfreeze) all weight and threshold changes.
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline]
+    fn synaptic(&mut self) -> impl Iterator<Item = (&mut Box<dyn PlasticityRule>, &RuleScope)> {
+        self.entries.iter_mut().filter(|e| e.enabled).filter_map(|e| match &mut e.rule {
+            StackRule::Synaptic(r) => Some((r, &e.scope)),
+            StackRule::Homeostatic(_) => None,
+        })
+    }
+
+    /// Forget the spike history of every rule, enabled or not.
+    pub(crate) fn reset(&mut self) {
+        for e in &mut self.entries {
+            match &mut e.rule {
+                StackRule::Synaptic(r) => r.reset(),
+                StackRule::Homeostatic(h) => h.reset(),
+            }
+        }
+    }
+
+    /// Follow `SnnRuntimePlus::remove_edges`: renumber edge-id scopes and per-edge rule
+    /// state of every entry. `removed` holds the old ids in ascending order.
+    pub(crate) fn on_edges_removed(&mut self, removed: &[u32]) {
+        for e in &mut self.entries {
+            e.scope.remap_removed_edges(removed);
+            if let StackRule::Synaptic(r) = &mut e.rule {
+                r.on_edges_removed(removed);
+            }
+        }
+    }
+
+    /// Independent copy of the stack; fails on the first entry whose rule does not support
+    /// `fork`.
+    pub(crate) fn fork(&self) -> Result<Self, ForkError> {
+        let entries = self
+            .entries
+            .iter()
+            .map(|e| {
+                let rule = match &e.rule {
+                    StackRule::Synaptic(r) => r.fork().map(StackRule::Synaptic),
+                    StackRule::Homeostatic(h) => h.fork().map(StackRule::Homeostatic),
+                };
+                let rule = rule.ok_or_else(|| ForkError { rule: e.name.clone() })?;
+                Ok(StackEntry { name: e.name.clone(), rule, scope: e.scope.clone(), enabled: e.enabled })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { entries, frozen: self.frozen })
+    }
+
+    pub(crate) fn decay(&mut self) {
+        for (r, _) in self.synaptic() {
+            r.decay();
+        }
+    }
+
+    /// Decay over `ticks` quiet ticks (fast-forward).
+    pub(crate) fn decay_ticks(&mut self, ticks: u64) {
+        for (r, _) in self.synaptic() {
+            r.decay_ticks(ticks);
+        }
+    }
+
+    /// Periods of the homeostatic rules that would run (none while frozen).
+    pub(crate) fn homeostatic_periods(&self) -> impl Iterator<Item = u64> + '_ {
+        let frozen = self.frozen;
+        self.entries.iter().filter(move |e| e.enabled && !frozen).filter_map(|e| match &e.rule {
+            StackRule::Homeostatic(h) => Some(h.period()),
+            StackRule::Synaptic(_) => None,
+        })
+    }
+
+    /// Register a spike of neuron `n` at `t` on both the pre and post side.
+    pub(crate) fn on_spike(&mut self, n: NeuronId, t: u64) {
+        for (r, _) in self.synaptic() {
+            r.on_pre_spike(n, t);
+            r.on_post_spike(n, t);
+        }
+    }
+
+    pub(crate) fn apply_pre(&mut self, edge: u32, pre: NeuronId, post: NeuronId, t: u64, weight: &mut i32) {
+        if self.frozen {
+            return;
+        }
+        for (r, scope) in self.synaptic() {
+            if scope.matches(edge, pre, post) {
+                r.apply_pre(edge, pre, post, t, weight);
+            }
+        }
+    }
+
+    pub(crate) fn apply_post(&mut self, edge: u32, pre: NeuronId, post: NeuronId, t: u64, weight: &mut i32) {
+        if self.frozen {
+            return;
+        }
+        for (r, scope) in self.synaptic() {
+            if scope.matches(edge, pre, post) {
+                r.apply_post(edge, pre, post, t, weight);
+            }
+        }
+    }
+
+    pub(crate) fn on_reward(&mut self, reward: i32) {
+        for (r, _) in self.synaptic() {
+            r.on_reward(reward);
+        }
+    }
+
+    /// Commit reward-gated updates of one edge; a rule runs if its scope matches any of
+    /// the edge's synapses.
+    pub(crate) fn consolidate(&mut self, edge: u32, sources: &[NeuronId], targets: &[NeuronId], weight: &mut i32) {
+        if self.frozen {
+            return;
+        }
+        for (r, scope) in self.synaptic() {
+            if sources.iter().any(|&s| targets.iter().any(|&t| scope.matches(edge, s, t))) {
+                r.consolidate_edge(edge, weight);
+            }
+        }
+    }
+
+    /// Enabled homeostatic rules (none while frozen).
+    pub(crate) fn homeostatic(&mut self) -> impl Iterator<Item = &mut Box<dyn HomeostaticRule>> {
+        let frozen = self.frozen;
+        self.entries.iter_mut().filter(move |e| e.enabled && !frozen).filter_map(|e| match &mut e.rule {
+            StackRule::Homeostatic(h) => Some(h),
+            StackRule::Synaptic(_) => None,
+        })
+    }
+}
+
+/// Build a synaptic rule with default parameters from its configuration name.
+/// Known names: "quantized_stdp", "pair_stdp_nearest", "pair_stdp_all_to_all",
+/// "triplet_stdp", "r_stdp".
+pub fn rule_by_name(name: &str) -> Option<Box<dyn PlasticityRule>> {
+    let rule: Box<dyn PlasticityRule> = match name {
+        "quantized_stdp" => Box::new(QuantizedStdp::with_defaults()),
+        "pair_stdp_nearest" => Box::new(PairStdp::nearest_with_defaults()),
+        "pair_stdp_all_to_all" => Box::new(PairStdp::all_to_all_with_defaults()),
+        "triplet_stdp" => Box::new(TripletStdp::with_defaults()),
+        "r_stdp" => Box::new(RewardModulatedStdp::with_defaults()),
+        _ => return None,
+    };
+    Some(rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stack_with_stdp(scope: RuleScope) -> PlasticityStack {
+        let mut stack = PlasticityStack::new();
+        stack.push_rule("stdp", QuantizedStdp::new(0.5, 0.0, 1.0, 1.0, 0.0, 4.0), scope);
+        stack.on_spike(0, 0);
+        stack
+    }
+
+    #[test]
+    fn test_scope_limits_updates() {
+        let mut stack = stack_with_stdp(RuleScope::targets(vec![2]));
+        let (mut w1, mut w2) = (0, 0);
+        stack.apply_post(0, 0, 1, 1, &mut w1);
+        stack.apply_post(1, 0, 2, 1, &mut w2);
+        assert_eq!(w1, 0);
+        assert!(w2 > 0);
+    }
+
+    #[test]
+    fn test_disable_freeze_and_remove() {
+        let mut stack = stack_with_stdp(RuleScope::All);
+        let mut w = 0;
+
+        assert!(stack.set_enabled("stdp", false));
+        stack.apply_post(0, 0, 1, 1, &mut w);
+        assert_eq!(w, 0);
+
+        stack.set_enabled("stdp", true);
+        stack.set_frozen(true);
+        stack.apply_post(0, 0, 1, 1, &mut w);
+        assert_eq!(w, 0);
+
+        stack.set_frozen(false);
+        stack.apply_post(0, 0, 1, 1, &mut w);
+        assert!(w > 0);
+
+        assert!(stack.remove("stdp").is_some());
+        assert!(stack.is_empty());
+        assert!(!stack.set_enabled("stdp", true));
+    }
+
+    #[test]
+    fn test_push_replaces_same_name() {
+        let mut stack = PlasticityStack::new();
+        stack.push_rule("a", QuantizedStdp::with_defaults(), RuleScope::All);
+        stack.push_rule("b", QuantizedStdp::with_defaults(), RuleScope::All);
+        stack.push_rule("a", QuantizedStdp::with_defaults(), RuleScope::edges(vec![3, 1]));
+        assert_eq!(stack.names().collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(stack.get("a").unwrap().scope, RuleScope::edges(vec![1, 3]));
+    }
+
+    #[test]
+    fn test_scope_ids_are_sorted_and_follow_removal() {
+        let mut scope = RuleScope::edges(vec![5, 1, 3, 1]);
+        assert!(scope.matches(1, 0, 0) && scope.matches(5, 0, 0) && !scope.matches(2, 0, 0));
+        scope.remap_removed_edges(&[0, 3]);
+        assert_eq!(scope, RuleScope::edges(vec![0, 3]));
+        assert!(RuleScope::sources(vec![9, 2]).matches(0, 9, 0));
+    }
+
+    #[test]
+    fn test_rule_by_name() {
+        for name in ["quantized_stdp", "pair_stdp_nearest", "pair_stdp_all_to_all", "triplet_stdp", "r_stdp"] {
+            assert!(rule_by_name(name).is_some(), "{}", name);
+        }
+        assert!(rule_by_name("nope").is_none());
+    }
+}
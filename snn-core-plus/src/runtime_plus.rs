@@ -1,190 +1,1192 @@
 //! Extended runtime that composes snn-core and adds:
-//! - source->edges adjacency index (avoids O(E) scans)
+//! - delivery and plasticity through the edge arena's source/target indexes (see `EdgeArena`)
 //! - optional per-tick processing budgets
-//! - optional plasticity hooks (behind the "plasticity" feature)
+//! - per-edge options (edge kind, short-term plasticity, synapse kernels) via add_edge_with()
+//! - optional Dale's-law enforcement: validation plus sign clamping after learning
+//! - two-phase (accumulate-then-threshold) delivery when `inner.step_mode` is TwoPhase
+//! - drop-or-defer handling of work truncated by budgets (OverflowPolicy)
+//! - per-tick telemetry via step_with_report() (StepReport)
+//! - wall-clock deadlines (StepBudgets::max_micros) and an adaptive edge-limit controller
+//! - per-neuron/per-edge delivery priority classes with per-class starvation stats
+//! - fast-forward over quiet ticks (run_until skips to the next event or periodic update)
+//! - typed control events from the wheel (current, parameter changes, rewards, markers)
+//! - optional plasticity hooks (behind the "plasticity" feature), including reward delivery
 //!
 //! Semantics:
-//! - step_once() returns "spikes at current tick" (the events popped from the wheel),
-//!   while scheduling any newly generated spikes for future ticks.
+//! - step_once() returns "spikes at current tick" (the events popped from the wheel, with
+//!   any control events after the spikes), while scheduling any newly generated spikes for
+//!   future ticks.
 
-use snn_core::{HyperEdge, Neuron, SpikeEvent, TimeWheel};
+use snn_core::{fixed_mul, to_fixed, EdgeArena, EdgeError, EdgeKind, EventKind, HyperEdge, InputAccumulator, NeuronId, RoundingWarning, RunSummary, SpikeEvent, StepMode, StopCondition, StopTracker, TimeBase, TimeWheel};
 use snn_core::SnnRuntime; // reuse inner data and time semantics
+use std::sync::Arc;
+
+use crate::deadline::{AdaptiveBudget, Clock, SystemClock};
+use crate::neuron_store::{NeuronStore, NeuronsView};
+use crate::short_term::{StpParams, StpState};
+use crate::synapse::{SynapseBank, SynapseKind};
 
 #[derive(Clone, Copy, Debug, Default)]
 pub struct StepBudgets {
     pub max_edge_visits: Option<usize>,
     pub max_spikes_scheduled: Option<usize>,
+    /// Wall-clock slice per tick in microseconds, measured with the runtime's Clock
+    pub max_micros: Option<u64>,
+}
+
+/// What one tick of `step_with_report` did.
+#[derive(Clone, Debug, Default)]
+pub struct StepReport {
+    /// Tick that was processed
+    pub tick: u64,
+    /// Start of that tick in milliseconds (see `set_time_base`)
+    pub time_ms: f64,
+    /// Spikes that occurred at this tick, followed by its control events (what
+    /// `step_once_with_budgets` returns)
+    pub spikes: Vec<SpikeEvent>,
+    /// Events popped from the wheel (excluding late deferred spikes)
+    pub popped: usize,
+    pub edges_visited: usize,
+    /// Target deliveries (membrane injections, accumulated or kernel inputs)
+    pub targets_injected: usize,
+    pub spikes_scheduled: usize,
+    /// Deferred work pending after the tick (deliveries + spikes)
+    pub deferred: usize,
+    /// Work discarded this tick: truncated deliveries, suppressed spikes, too-late work
+    pub dropped: usize,
+    /// The edge-visit budget stopped delivery
+    pub edge_budget_hit: bool,
+    /// The spike budget suppressed at least one spike
+    pub spike_budget_hit: bool,
+    /// The wall-clock deadline stopped delivery
+    pub time_budget_hit: bool,
+    /// Measured duration of the tick in microseconds
+    pub elapsed_micros: u64,
+    /// Synapse updates applied by plasticity rules (0 without the "plasticity" feature)
+    pub plasticity_updates: usize,
+}
+
+impl StepReport {
+    pub fn budget_hit(&self) -> bool {
+        self.edge_budget_hit || self.spike_budget_hit || self.time_budget_hit
+    }
+}
+
+/// How late deferred work may run (see `OverflowPolicy::Defer`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Lateness {
+    /// Keep original timestamps: spikes report their original time, deliveries use the
+    /// original spike time for delays and traces
+    #[default]
+    Keep,
+    /// Restamp late work to the tick in which it actually runs
+    Restamp,
+    /// Keep timestamps but drop work that is more than this many ticks late
+    DropAfter(u64),
+}
+
+impl Lateness {
+    /// Apply the policy to work stamped `*time` that runs at `now`; false = drop it.
+    #[inline]
+    fn admit(self, time: &mut u64, now: u64) -> bool {
+        match self {
+            Lateness::Keep => true,
+            Lateness::Restamp => {
+                *time = (*time).max(now);
+                true
+            }
+            Lateness::DropAfter(max) => now.saturating_sub(*time) <= max,
+        }
+    }
+}
+
+/// What happens to work cut off by `StepBudgets`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Truncated deliveries and suppressed spikes are discarded
+    #[default]
+    Drop,
+    /// Truncated deliveries resume at the next edge on the next tick (ahead of new events)
+    /// and suppressed spikes are re-admitted against the next tick's spike budget. Spikes
+    /// whose time has passed occur in the tick they are admitted, subject to `Lateness`.
+    /// Resume positions index the source's outgoing edges; `remove_edges` (and so structural
+    /// pruning) re-points them, and edges added in between come after them.
+    Defer(Lateness),
+}
+
+/// Why `SnnRuntimePlus::fork` failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForkError {
+    /// Name of the plasticity stack entry whose rule does not implement `fork`
+    pub rule: String,
+}
+
+/// Let a plasticity rule update `weight` as a magnitude: inhibitory weights are negated
+/// around `update`, so rules bounded to `[w_min, w_max]` with `w_min >= 0` strengthen or
+/// weaken inhibitory edges instead of clamping them to 0.
+#[cfg(feature = "plasticity")]
+#[inline]
+fn with_magnitude(kind: EdgeKind, weight: &mut i32, update: impl FnOnce(&mut i32)) {
+    if kind == EdgeKind::Inhibitory {
+        let mut magnitude = weight.saturating_neg();
+        update(&mut magnitude);
+        *weight = magnitude.saturating_neg();
+    } else {
+        update(weight);
+    }
+}
+
+/// A spike whose delivery was cut off; resumes at `edge_pos` in its source's edge list
+/// (only that edge when `single`, otherwise through the end of the list).
+#[derive(Clone, Copy, Debug)]
+struct DeferredDelivery {
+    event: SpikeEvent,
+    edge_pos: usize,
+    single: bool,
+}
+
+/// Delivery counts of one priority class, accumulated over ticks (unit: edge visits).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClassStats {
+    pub delivered: u64,
+    /// Visits cut by a budget and carried over (OverflowPolicy::Defer)
+    pub deferred: u64,
+    /// Visits cut by a budget and discarded (OverflowPolicy::Drop)
+    pub dropped: u64,
+}
+
+#[inline]
+fn class_stats(stats: &mut Vec<ClassStats>, class: u8) -> &mut ClassStats {
+    let idx = class as usize;
+    if stats.len() <= idx {
+        stats.resize(idx + 1, ClassStats::default());
+    }
+    &mut stats[idx]
+}
+
+/// Per-edge options for `add_edge_with`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EdgeOptions {
+    /// Edge kind; None = derived from the weight's sign
+    pub kind: Option<EdgeKind>,
+    /// Short-term plasticity (Tsodyks–Markram); None = static synapse
+    pub stp: Option<StpParams>,
+    /// Synapse kernel (Instant = direct membrane jump)
+    pub synapse: SynapseKind,
+    /// Delivery priority class (higher is served first); None = derived from the neurons
+    pub priority: Option<u8>,
+}
+
+/// Per-edge runtime state kept parallel to `inner.edges` (index == edge id).
+#[derive(Clone, Debug, Default)]
+struct EdgeMeta {
+    stp: Option<StpState>,
+    // Kernel channel in `synapses` (None = instant delivery)
+    channel: Option<u16>,
+    // Explicit priority class, and the effective class used for delivery order
+    priority: Option<u8>,
+    class: u8,
+}
+
+#[inline]
+fn edge_class(meta: &[EdgeMeta], eid: u32) -> u8 {
+    meta.get(eid as usize).map_or(0, |m| m.class)
 }
 
 pub struct SnnRuntimePlus {
+    /// Edges, wheel and time base; `inner.neurons` stays empty (neurons live in `neurons`)
     pub inner: SnnRuntime,
-    // Adjacency: for each source neuron id -> list of edge ids originating from it
-    source_to_edges: Vec<Vec<u32>>,
+    // Neuron state, structure-of-arrays
+    neurons: NeuronStore,
+    // Per-edge state beyond HyperEdge (missing entries behave as default)
+    edge_meta: Vec<EdgeMeta>,
+    // Current/conductance kernels with per-neuron synaptic state
+    synapses: SynapseBank,
+    // Keep every neuron's outgoing weights on one sign under learning
+    dales_law: bool,
+    // Summed instant inputs of the current tick (two-phase mode)
+    accumulator: InputAccumulator,
+    // Budget overflow handling and the work carried over to the next tick
+    overflow: OverflowPolicy,
+    deferred_deliveries: Vec<DeferredDelivery>,
+    deferred_spikes: Vec<SpikeEvent>,
+    // Time source for deadlines and step durations, and the optional edge-limit controller
+    clock: Box<dyn Clock + Send>,
+    adaptive: Option<AdaptiveBudget>,
+    // Whether any neuron or edge priority class is set, and per-class stats
+    prioritized: bool,
+    priority_stats: Vec<ClassStats>,
 
     #[cfg(feature = "plasticity")]
-    plasticity: Option<Box<dyn crate::plasticity::PlasticityRule>>,
+    plasticity: crate::plasticity_stack::PlasticityStack,
+    #[cfg(feature = "plasticity")]
+    structural: Option<crate::structural::StructuralPlasticity>,
+    #[cfg(feature = "plasticity")]
+    structural_events: Vec<crate::structural::StructuralEvent>,
 }
 
 impl SnnRuntimePlus {
     pub fn new(wheel_size: u64) -> Self {
         Self {
             inner: SnnRuntime::new(wheel_size),
-            source_to_edges: Vec::new(),
+            neurons: NeuronStore::default(),
+            edge_meta: Vec::new(),
+            synapses: SynapseBank::default(),
+            dales_law: false,
+            accumulator: InputAccumulator::default(),
+            overflow: OverflowPolicy::Drop,
+            deferred_deliveries: Vec::new(),
+            deferred_spikes: Vec::new(),
+            clock: Box::new(SystemClock::new()),
+            adaptive: None,
+            prioritized: false,
+            priority_stats: Vec::new(),
+            #[cfg(feature = "plasticity")]
+            plasticity: crate::plasticity_stack::PlasticityStack::new(),
             #[cfg(feature = "plasticity")]
-            plasticity: None,
+            structural: None,
+            #[cfg(feature = "plasticity")]
+            structural_events: Vec::new(),
         }
     }
 
-    pub fn from_inner(inner: SnnRuntime) -> Self {
+    /// Runtime that sizes its wheel from the longest edge delay (see `SnnRuntime::auto_sized`).
+    pub fn auto_sized() -> Self {
+        Self::from_inner(SnnRuntime::auto_sized())
+    }
+
+    pub fn from_inner(mut inner: SnnRuntime) -> Self {
+        let neurons = NeuronStore::from(core::mem::take(&mut inner.neurons));
         let mut me = Self {
             inner,
-            source_to_edges: Vec::new(),
+            neurons,
+            edge_meta: Vec::new(),
+            synapses: SynapseBank::default(),
+            dales_law: false,
+            accumulator: InputAccumulator::default(),
+            overflow: OverflowPolicy::Drop,
+            deferred_deliveries: Vec::new(),
+            deferred_spikes: Vec::new(),
+            clock: Box::new(SystemClock::new()),
+            adaptive: None,
+            prioritized: false,
+            priority_stats: Vec::new(),
+            #[cfg(feature = "plasticity")]
+            plasticity: crate::plasticity_stack::PlasticityStack::new(),
+            #[cfg(feature = "plasticity")]
+            structural: None,
             #[cfg(feature = "plasticity")]
-            plasticity: None,
+            structural_events: Vec::new(),
         };
-        // Build adjacency from existing edges if any
-        me.rebuild_adjacency();
+        me.edge_meta.resize_with(me.inner.edges().len(), EdgeMeta::default);
         me
     }
 
-    fn ensure_neuron_capacity(&mut self, id: u32) {
-        let len_needed = (id as usize) + 1;
-        if self.source_to_edges.len() < len_needed {
-            self.source_to_edges.resize_with(len_needed, || Vec::new());
-        }
+    /// The plain runtime, with the neurons moved back into `SnnRuntime::neurons`.
+    pub fn into_inner(self) -> SnnRuntime {
+        let mut inner = self.inner;
+        inner.neurons = self.neurons.view().iter().collect();
+        inner
     }
 
-    fn rebuild_adjacency(&mut self) {
-        // Build into a temporary to avoid aliasing & self borrows
-        let mut adj: Vec<Vec<u32>> = vec![Vec::new(); self.inner.neurons.len()];
-        for edge in &self.inner.edges {
-            for &s in &edge.sources {
-                let idx = s as usize;
-                if idx < adj.len() {
-                    adj[idx].push(edge.id);
-                }
+    pub fn add_neuron(&mut self, threshold: f32) -> NeuronId {
+        self.neurons.push(threshold)
+    }
+
+    pub fn add_edge(&mut self, sources: Vec<NeuronId>, targets: Vec<NeuronId>, weight: f32, delay: u64) {
+        self.add_edge_with(sources, targets, weight, delay, EdgeOptions::default());
+    }
+
+    /// Add an edge with per-edge options; returns the new edge id. Panics if the delay does
+    /// not fit a fixed-size wheel (see `try_add_edge_with`).
+    pub fn add_edge_with(
+        &mut self,
+        sources: Vec<NeuronId>,
+        targets: Vec<NeuronId>,
+        weight: f32,
+        delay: u64,
+        opts: EdgeOptions,
+    ) -> u32 {
+        // Edge id equals index in snn-core (by construction)
+        let next_id = self.inner.edges().len() as u32;
+        let kind = opts.kind.unwrap_or_else(|| EdgeKind::from_weight(to_fixed(weight)));
+        self.inner.add_typed_edge(sources, targets, weight, delay, kind);
+        self.edge_meta.resize_with(next_id as usize, EdgeMeta::default);
+        let channel = self.synapses.channel_for(opts.synapse);
+        let meta = EdgeMeta { stp: opts.stp.map(StpState::new), channel, priority: opts.priority, class: 0 };
+        self.edge_meta.push(meta);
+        self.prioritized |= opts.priority.is_some_and(|c| c > 0);
+        self.refresh_edge_class(next_id);
+        next_id
+    }
+
+    /// `add_edge_with` that adds nothing and returns `DelayBeyondHorizon` when the delay does
+    /// not fit the wheel and growth is disabled (see `set_wheel_growth`).
+    pub fn try_add_edge_with(
+        &mut self,
+        sources: Vec<NeuronId>,
+        targets: Vec<NeuronId>,
+        weight: f32,
+        delay: u64,
+        opts: EdgeOptions,
+    ) -> Result<u32, EdgeError> {
+        self.inner.reserve_delay(self.inner.edges().len() as u32, delay)?;
+        Ok(self.add_edge_with(sources, targets, weight, delay, opts))
+    }
+
+    /// Allow (default) or forbid growing the wheel for long delays; see
+    /// `SnnRuntime::set_wheel_growth`.
+    pub fn set_wheel_growth(&mut self, enabled: bool) {
+        self.inner.set_wheel_growth(enabled);
+    }
+
+    /// Longest edge delay added so far, in ticks.
+    pub fn max_delay(&self) -> u64 {
+        self.inner.max_delay()
+    }
+
+    /// `add_edge` with the delay in milliseconds (rounded to whole ticks); returns its id.
+    pub fn add_edge_ms(&mut self, sources: Vec<NeuronId>, targets: Vec<NeuronId>, weight: f32, delay_ms: f64) -> u32 {
+        self.add_edge_with_ms(sources, targets, weight, delay_ms, EdgeOptions::default())
+    }
+
+    /// `add_edge_with` in physical units: the delay and the time constants of `opts.synapse`
+    /// and `opts.stp` are milliseconds. Rounded delays are reported by `take_time_warnings`.
+    pub fn add_edge_with_ms(
+        &mut self,
+        sources: Vec<NeuronId>,
+        targets: Vec<NeuronId>,
+        weight: f32,
+        delay_ms: f64,
+        opts: EdgeOptions,
+    ) -> u32 {
+        let tb = self.inner.time_base();
+        let delay = self.inner.ms_to_ticks(delay_ms);
+        let opts = EdgeOptions { synapse: opts.synapse.to_ticks(&tb), stp: opts.stp.map(|p| p.to_ticks(&tb)), ..opts };
+        self.add_edge_with(sources, targets, weight, delay, opts)
+    }
+
+    /// Set the physical duration of one tick (default 1 ms); see `TimeBase`.
+    pub fn set_time_base(&mut self, time_base: TimeBase) {
+        self.inner.set_time_base(time_base);
+    }
+
+    pub fn time_base(&self) -> TimeBase {
+        self.inner.time_base()
+    }
+
+    /// Current time (start of the next tick to run) in milliseconds.
+    pub fn now_ms(&self) -> f64 {
+        self.inner.now_ms()
+    }
+
+    /// Take the rounding warnings recorded by `*_ms` calls since the last call.
+    pub fn take_time_warnings(&mut self) -> Vec<RoundingWarning> {
+        self.inner.take_time_warnings()
+    }
+
+    /// Clear the dynamic state and keep the network (see `SnnRuntime::reset_state`). Also
+    /// drops deferred work, rests short-term plasticity and synapse kernels, clears
+    /// plasticity traces and homeostatic windows, restarts structural plasticity from its
+    /// seed and zeroes the priority stats. Weights, thresholds and installed rules are kept.
+    pub fn reset_state(&mut self) {
+        self.inner.reset_state();
+        self.neurons.reset_state();
+        self.deferred_deliveries.clear();
+        self.deferred_spikes.clear();
+        self.accumulator = InputAccumulator::default();
+        for stp in self.edge_meta.iter_mut().filter_map(|m| m.stp.as_mut()) {
+            stp.reset();
+        }
+        self.synapses.reset();
+        self.reset_priority_stats();
+        #[cfg(feature = "plasticity")]
+        {
+            self.plasticity.reset();
+            if let Some(engine) = self.structural.as_mut() {
+                engine.reset();
             }
+            self.structural_events.clear();
         }
-        self.source_to_edges = adj;
     }
 
-    pub fn add_neuron(&mut self, threshold: f32) -> u32 {
-        let id = self.inner.add_neuron(threshold);
-        self.ensure_neuron_capacity(id);
-        id
+    /// Copy for what-if runs. The edge arena stays shared with this runtime until
+    /// either side changes them (the first learning or structural update copies them);
+    /// neuron, wheel, kernel, short-term and plasticity state are copied. The fork uses
+    /// `Clock::fork` of this runtime's clock. Unlike `SnnRuntime::fork`, which cannot fail,
+    /// this returns an error naming the first installed plasticity or homeostatic rule that
+    /// does not support `fork`.
+    pub fn fork(&self) -> Result<Self, ForkError> {
+        Ok(Self {
+            inner: self.inner.fork(),
+            neurons: self.neurons.clone(),
+            edge_meta: self.edge_meta.clone(),
+            synapses: self.synapses.clone(),
+            dales_law: self.dales_law,
+            accumulator: self.accumulator.clone(),
+            overflow: self.overflow,
+            deferred_deliveries: self.deferred_deliveries.clone(),
+            deferred_spikes: self.deferred_spikes.clone(),
+            clock: self.clock.fork(),
+            adaptive: self.adaptive.clone(),
+            prioritized: self.prioritized,
+            priority_stats: self.priority_stats.clone(),
+            #[cfg(feature = "plasticity")]
+            plasticity: self.plasticity.fork()?,
+            #[cfg(feature = "plasticity")]
+            structural: self.structural.clone(),
+            #[cfg(feature = "plasticity")]
+            structural_events: self.structural_events.clone(),
+        })
     }
 
-    pub fn add_edge(&mut self, sources: Vec<u32>, targets: Vec<u32>, weight: f32, delay: u64) {
-        // Edge id equals index in snn-core (by construction)
-        let next_id = self.inner.edges.len() as u32;
-        self.inner.add_edge(sources.clone(), targets, weight, delay);
-        // Update adjacency
-        for s in sources {
-            self.ensure_neuron_capacity(s);
-            self.source_to_edges[s as usize].push(next_id);
+    /// Choose whether budget-truncated work is dropped or deferred to the next tick.
+    /// Switching to Drop discards pending deferred work.
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.overflow = policy;
+        if policy == OverflowPolicy::Drop {
+            self.deferred_deliveries.clear();
+            self.deferred_spikes.clear();
+        }
+    }
+
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow
+    }
+
+    /// Pending deferred work: (truncated deliveries, suppressed spikes).
+    pub fn deferred_counts(&self) -> (usize, usize) {
+        (self.deferred_deliveries.len(), self.deferred_spikes.len())
+    }
+
+    /// Set the delivery priority class of a neuron (higher is served first, default 0).
+    /// Edges without an explicit class take the highest class among their sources and
+    /// targets, so e.g. output populations or inhibitory interneurons can be served first
+    /// when budgets truncate a tick; the rest is deferred or dropped per OverflowPolicy.
+    /// The class is stored in the time wheel (`TimeWheel::set_priority`, which also orders
+    /// events under `EventOrder::Priority`); set it here rather than through `queue()` so
+    /// the neuron's edges are reclassified.
+    pub fn set_neuron_priority(&mut self, neuron_id: NeuronId, class: u8) {
+        self.inner.queue.set_priority(neuron_id, class);
+        self.prioritized |= class > 0;
+        self.inner.compile();
+        let edges = self.inner.edges();
+        let touched: Vec<u32> = edges.outgoing(neuron_id).iter().chain(edges.incoming(neuron_id)).copied().collect();
+        for eid in touched {
+            self.refresh_edge_class(eid);
+        }
+    }
+
+    /// Set an explicit delivery priority class for an edge (overrides its neurons' classes).
+    pub fn set_edge_priority(&mut self, edge_id: u32, class: u8) {
+        if let Some(m) = self.edge_meta.get_mut(edge_id as usize) {
+            m.priority = Some(class);
+            self.prioritized |= class > 0;
+            self.refresh_edge_class(edge_id);
+        }
+    }
+
+    /// Effective delivery priority class of an edge.
+    pub fn edge_priority(&self, edge_id: u32) -> u8 {
+        edge_class(&self.edge_meta, edge_id)
+    }
+
+    /// Per-class delivery statistics (index = class), accumulated since the last reset.
+    pub fn priority_stats(&self) -> &[ClassStats] {
+        &self.priority_stats
+    }
+
+    pub fn reset_priority_stats(&mut self) {
+        self.priority_stats.clear();
+    }
+
+    fn refresh_edge_class(&mut self, edge_id: u32) {
+        let (Some(edge), Some(meta)) = (self.inner.edges().get(edge_id), self.edge_meta.get_mut(edge_id as usize)) else {
+            return;
+        };
+        meta.class = meta.priority.unwrap_or_else(|| {
+            edge.sources
+                .iter()
+                .chain(edge.targets)
+                .map(|&n| self.inner.queue.priority(n))
+                .max()
+                .unwrap_or(0)
+        });
+    }
+
+    /// Replace the clock used for `max_micros` deadlines and step durations.
+    pub fn set_clock<C: Clock + 'static>(&mut self, clock: C) {
+        self.clock = Box::new(clock);
+    }
+
+    /// Install (or remove) an adaptive controller; while installed, `max_edge_visits` is
+    /// capped by its limit, which it retunes after every tick.
+    pub fn set_adaptive_budget(&mut self, controller: Option<AdaptiveBudget>) {
+        self.adaptive = controller;
+    }
+
+    pub fn adaptive_budget(&self) -> Option<&AdaptiveBudget> {
+        self.adaptive.as_ref()
+    }
+
+    /// Select immediate or two-phase (order-independent) delivery; see `StepMode`.
+    pub fn set_step_mode(&mut self, mode: StepMode) {
+        self.inner.set_step_mode(mode);
+    }
+
+    /// Enforce Dale's law: after every plasticity or homeostatic update, excitatory and
+    /// inhibitory weights are clamped to their sign. Use `validate` to check the topology.
+    pub fn set_dales_law(&mut self, enabled: bool) {
+        self.dales_law = enabled;
+    }
+
+    pub fn dales_law(&self) -> bool {
+        self.dales_law
+    }
+
+    /// Validate edge signs (and Dale's law, if enabled); see `SnnRuntime::validate`.
+    pub fn validate(&self) -> Result<(), EdgeError> {
+        self.inner.validate(self.dales_law)
+    }
+
+    /// Clamp every weight to its kind's sign (Dale's law only).
+    #[cfg(feature = "plasticity")]
+    fn clamp_signs(&mut self) {
+        if self.dales_law {
+            self.inner.edges_mut().clamp_to_kinds();
         }
     }
 
+    /// Synapse kernel channels (per-neuron synaptic state, for diagnostics).
+    pub fn synapses(&self) -> &SynapseBank {
+        &self.synapses
+    }
+
+    /// Short-term plasticity state of an edge, if configured.
+    pub fn stp_state(&self, edge_id: u32) -> Option<&StpState> {
+        self.edge_meta.get(edge_id as usize).and_then(|m| m.stp.as_ref())
+    }
+
     pub fn queue(&mut self) -> &mut TimeWheel {
         &mut self.inner.queue
     }
 
-    pub fn neurons(&self) -> &Vec<Neuron> {
-        &self.inner.neurons
+    /// Neuron state by field (`neurons().membrane[id]`); `get`/`iter` give `Neuron` copies.
+    pub fn neurons(&self) -> NeuronsView<'_> {
+        self.neurons.view()
     }
 
-    pub fn neurons_mut(&mut self) -> &mut Vec<Neuron> {
-        &mut self.inner.neurons
+    pub fn neurons_mut(&mut self) -> &mut NeuronStore {
+        &mut self.neurons
     }
 
-    pub fn edges(&self) -> &Vec<HyperEdge> {
-        &self.inner.edges
+    pub fn edges(&self) -> &EdgeArena {
+        self.inner.edges()
     }
 
-    pub fn edges_mut(&mut self) -> &mut Vec<HyperEdge> {
-        &mut self.inner.edges
+    /// Edges for in-place weight changes; add and remove them with `add_edge_with` and
+    /// `remove_edges`, which keep the per-edge state in step.
+    pub fn edges_mut(&mut self) -> &mut EdgeArena {
+        self.inner.edges_mut()
     }
 
+    /// Replace all synaptic rules with `rule` (named "default", applied to every edge).
+    /// Homeostatic rules in the stack are kept.
     #[cfg(feature = "plasticity")]
     pub fn set_plasticity<R: crate::plasticity::PlasticityRule + 'static>(&mut self, rule: R) {
-        self.plasticity = Some(Box::new(rule));
+        self.plasticity.clear_synaptic();
+        self.plasticity.push_rule("default", rule, crate::plasticity_stack::RuleScope::All);
+    }
+
+    /// Add (or replace) a named synaptic rule restricted to `scope`.
+    #[cfg(feature = "plasticity")]
+    pub fn add_plasticity<R: crate::plasticity::PlasticityRule + 'static>(
+        &mut self,
+        name: &str,
+        rule: R,
+        scope: crate::plasticity_stack::RuleScope,
+    ) {
+        self.plasticity.push_rule(name, rule, scope);
+    }
+
+    /// Add (or replace) a named homeostatic mechanism; it observes every tick and applies
+    /// once per its period.
+    #[cfg(feature = "plasticity")]
+    pub fn add_homeostasis<H: crate::homeostasis::HomeostaticRule + 'static>(&mut self, name: &str, rule: H) {
+        self.plasticity.push_homeostasis(name, rule);
+    }
+
+    /// Remove the rule called `name` from the stack; returns false if there is none.
+    #[cfg(feature = "plasticity")]
+    pub fn remove_plasticity(&mut self, name: &str) -> bool {
+        self.plasticity.remove(name).is_some()
+    }
+
+    /// Freeze (or unfreeze) all learning: weights and thresholds stay fixed, structural
+    /// plasticity pauses, and spike traces keep updating.
+    #[cfg(feature = "plasticity")]
+    pub fn freeze_weights(&mut self, frozen: bool) {
+        self.plasticity.set_frozen(frozen);
+    }
+
+    #[cfg(feature = "plasticity")]
+    pub fn plasticity(&self) -> &crate::plasticity_stack::PlasticityStack {
+        &self.plasticity
+    }
+
+    /// Rule stack access (enable/disable by name, scopes, lookup).
+    #[cfg(feature = "plasticity")]
+    pub fn plasticity_mut(&mut self) -> &mut crate::plasticity_stack::PlasticityStack {
+        &mut self.plasticity
+    }
+
+    /// Feed this tick's spikes to homeostatic rules and apply those whose period elapsed.
+    #[cfg(feature = "plasticity")]
+    fn apply_homeostasis(&mut self, events: &[SpikeEvent]) {
+        let elapsed = self.inner.queue.current_time;
+        let mut applied = false;
+        for rule in self.plasticity.homeostatic() {
+            rule.observe(events);
+            if elapsed.is_multiple_of(rule.period()) {
+                applied = true;
+                rule.apply(crate::homeostasis::HomeostasisView {
+                    neurons: &mut self.neurons,
+                    edges: self.inner.edges_mut(),
+                });
+            }
+        }
+        if applied {
+            self.clamp_signs();
+        }
+    }
+
+    /// Enable structural plasticity (pruning of weak edges, growth between co-active neurons).
+    #[cfg(feature = "plasticity")]
+    pub fn set_structural_plasticity(&mut self, config: crate::structural::StructuralConfig) {
+        self.structural = Some(crate::structural::StructuralPlasticity::new(config));
+    }
+
+    /// Take the structural changes recorded since the last call (for observers/TUI).
+    #[cfg(feature = "plasticity")]
+    pub fn take_structural_events(&mut self) -> Vec<crate::structural::StructuralEvent> {
+        core::mem::take(&mut self.structural_events)
+    }
+
+    /// Remove the given edges and renumber the remaining ones (id == index, see
+    /// `EdgeArena::remove`); per-edge state and edge-id rule scopes follow the new ids, and
+    /// deferred deliveries skip the removed edges. Returns the removed edges in ascending id order.
+    pub fn remove_edges(&mut self, ids: &[u32]) -> Vec<HyperEdge> {
+        let mut ids = ids.to_vec();
+        ids.sort_unstable();
+        ids.dedup();
+        let gone = |e: &u32| ids.binary_search(e).is_ok();
+
+        // Deferred deliveries hold positions in their source's outgoing list: shift them past
+        // the removed edges (outgoing lists stay in id order) and drop single-edge ones that go
+        self.inner.compile();
+        let edges = self.inner.edges();
+        self.deferred_deliveries.retain_mut(|d| {
+            let out = edges.outgoing(d.event.neuron_id);
+            if d.single && out.get(d.edge_pos).is_some_and(gone) {
+                return false;
+            }
+            d.edge_pos -= out[..d.edge_pos.min(out.len())].iter().filter(|e| gone(e)).count();
+            true
+        });
+        let removed = self.inner.edges_mut().remove(&ids);
+        if !removed.is_empty() {
+            self.edge_meta.resize_with(self.inner.edges().len() + removed.len(), EdgeMeta::default);
+            let mut id = 0;
+            self.edge_meta.retain(|_| {
+                id += 1;
+                !gone(&(id - 1))
+            });
+            #[cfg(feature = "plasticity")]
+            {
+                let old_ids: Vec<u32> = removed.iter().map(|e| e.id).collect();
+                self.plasticity.on_edges_removed(&old_ids);
+                if let Some(engine) = self.structural.as_mut() {
+                    engine.on_edges_removed(&old_ids);
+                }
+            }
+        }
+        removed
+    }
+
+    /// Run the structural engine after the tick and apply its plan.
+    #[cfg(feature = "plasticity")]
+    fn apply_structural(&mut self, events: &[SpikeEvent]) {
+        use crate::structural::StructuralEvent;
+        let now = self.inner.queue.current_time;
+        if self.plasticity.is_frozen() {
+            return;
+        }
+        let Some(engine) = self.structural.as_mut() else {
+            return;
+        };
+        engine.observe(events);
+        if !engine.due(now) {
+            return;
+        }
+        let plan = engine.plan(now, self.inner.edges());
+        let (weight, delay) = (engine.config().grow_weight, engine.config().grow_delay);
+
+        for edge in self.remove_edges(&plan.prune) {
+            self.structural_events.push(StructuralEvent::Pruned {
+                time: now,
+                edge_id: edge.id,
+                sources: edge.sources,
+                targets: edge.targets,
+            });
+        }
+        // Under Dale's law a grown edge takes the sign of the source's first excitatory or
+        // inhibitory edge (looked up before growing, while the arena's indexes are current)
+        let edges = self.inner.edges();
+        let kinds: Vec<EdgeKind> = plan
+            .grow
+            .iter()
+            .map(|&(source, _)| {
+                let typed = edges.outgoing(source).iter().map(|&e| edges.kind(e)).find(|k| k.sign() != 0);
+                match typed {
+                    Some(kind) if self.dales_law => kind,
+                    _ => EdgeKind::from_weight(to_fixed(weight)),
+                }
+            })
+            .collect();
+        for ((source, target), kind) in plan.grow.into_iter().zip(kinds) {
+            let w = if kind == EdgeKind::Inhibitory { -weight.abs() } else { weight };
+            let opts = EdgeOptions { kind: Some(kind), ..Default::default() };
+            // Nothing grows when the delay does not fit a fixed-size wheel
+            let Ok(edge_id) = self.try_add_edge_with(vec![source], vec![target], w, delay, opts) else {
+                continue;
+            };
+            self.structural_events.push(StructuralEvent::Grown { time: now, edge_id, source, target });
+        }
+    }
+
+    /// Deliver a reward/neuromodulator signal to the plasticity rules.
+    /// Reward-gated rules (e.g. R-STDP) then commit their eligibility into every
+    /// `source -> target` pair of every edge in their scope; other rules ignore the signal.
+    #[cfg(feature = "plasticity")]
+    pub fn deliver_reward(&mut self, value: f32) {
+        let p = &mut self.plasticity;
+        if !p.is_active() {
+            return;
+        }
+        p.on_reward(snn_core::to_fixed(value));
+        for edge in self.inner.edges_mut().iter_mut() {
+            if matches!(edge.kind, EdgeKind::Modulatory(_)) {
+                continue;
+            }
+            with_magnitude(edge.kind, edge.weight, |w| p.consolidate(edge.id, edge.sources, edge.targets, w));
+        }
+        self.clamp_signs();
+    }
+
+    /// Apply a control event (anything but a spike) like `SnnRuntime::apply_control`;
+    /// rewards go to the plasticity rules (ignored without the "plasticity" feature).
+    pub fn apply_control(&mut self, event: SpikeEvent) {
+        match event.kind {
+            EventKind::Current(amount) => {
+                let fired = self.neurons.inject(event.neuron_id, amount, event.time);
+                if fired {
+                    let spike = SpikeEvent::spike(event.neuron_id, event.time).with_offset(event.offset);
+                    let _ = self.inner.queue.schedule_clamped(spike);
+                }
+            }
+            EventKind::SetParam(param, value) => self.neurons.set_param(event.neuron_id, param, value),
+            #[cfg(feature = "plasticity")]
+            EventKind::Reward(amount) => self.deliver_reward(snn_core::from_fixed(amount)),
+            _ => {}
+        }
+    }
+
+    /// Run pair-based plasticity for the spikes of this tick.
+    /// Rules see the weight magnitude of inhibitory edges (see `with_magnitude`).
+    /// Pass 1 lets every spike act as "post" on its incoming edges (LTP) and as "pre" on
+    /// its outgoing edges (LTD) against traces from earlier ticks; pass 2 then adds the
+    /// spikes to the traces, so same-tick pairs are ignored regardless of event order.
+    /// Modulatory edges are not learned. Returns the number of synapse updates applied.
+    #[cfg(feature = "plasticity")]
+    fn apply_plasticity(&mut self, events: &[SpikeEvent]) -> usize {
+        let p = &mut self.plasticity;
+        if !p.is_active() {
+            return 0;
+        }
+        // Edge lists are re-read by index so each edge can be borrowed mutably in turn
+        let edges = self.inner.edges_mut();
+        let mut updates = 0;
+        for ev in events {
+            let n = ev.neuron_id;
+            for i in 0..edges.incoming(n).len() {
+                let edge = edges.edge_mut(edges.incoming(n)[i]);
+                if matches!(edge.kind, EdgeKind::Modulatory(_)) {
+                    continue;
+                }
+                with_magnitude(edge.kind, edge.weight, |w| {
+                    for &pre in edge.sources {
+                        p.apply_post(edge.id, pre, n, ev.time, w);
+                    }
+                });
+                updates += edge.sources.len();
+            }
+            for i in 0..edges.outgoing(n).len() {
+                let edge = edges.edge_mut(edges.outgoing(n)[i]);
+                if matches!(edge.kind, EdgeKind::Modulatory(_)) {
+                    continue;
+                }
+                with_magnitude(edge.kind, edge.weight, |w| {
+                    for &post in edge.targets {
+                        p.apply_pre(edge.id, n, post, ev.time, w);
+                    }
+                });
+                updates += edge.targets.len();
+            }
+        }
+        for ev in events {
+            p.on_spike(ev.neuron_id, ev.time);
+        }
+        if self.dales_law {
+            for ev in events {
+                let n = ev.neuron_id;
+                for i in 0..edges.incoming(n).len() {
+                    edges.edge_mut(edges.incoming(n)[i]).clamp_to_kind();
+                }
+                for i in 0..edges.outgoing(n).len() {
+                    edges.edge_mut(edges.outgoing(n)[i]).clamp_to_kind();
+                }
+            }
+        }
+        if p.is_frozen() {
+            0
+        } else {
+            updates
+        }
     }
 
     /// Advance one tick with optional processing budgets.
-    /// Returns the spikes that occurred at the current tick (the popped events).
+    /// Returns the spikes that occurred at the current tick (the popped events, plus deferred
+    /// spikes admitted late under `OverflowPolicy::Defer`).
     pub fn step_once_with_budgets(&mut self, budgets: StepBudgets) -> Vec<SpikeEvent> {
+        self.step_with_report(budgets).spikes
+    }
+
+    /// Advance one tick with optional processing budgets and report what the tick did.
+    /// Counting is a handful of integer increments, so it is cheap enough for every tick.
+    pub fn step_with_report(&mut self, budgets: StepBudgets) -> StepReport {
+        let start = self.clock.now_micros();
+        let budgets = match &self.adaptive {
+            Some(a) => StepBudgets {
+                max_edge_visits: Some(budgets.max_edge_visits.map_or(a.limit(), |m| m.min(a.limit()))),
+                ..budgets
+            },
+            None => budgets,
+        };
+        let deadline = budgets.max_micros.map(|us| start.saturating_add(us));
         let mut edge_visits: usize = 0;
         let mut spikes_scheduled: usize = 0;
+        let mut injected: usize = 0;
+        let mut dropped: usize = 0;
+        let (mut edge_budget_hit, mut spike_budget_hit, mut time_budget_hit) = (false, false, false);
+        let spike_room = |n: usize| budgets.max_spikes_scheduled.is_none_or(|max| n < max);
+        let two_phase = self.inner.step_mode == StepMode::TwoPhase;
+        let lateness = match self.overflow {
+            OverflowPolicy::Defer(l) => Some(l),
+            OverflowPolicy::Drop => None,
+        };
 
         #[cfg(feature = "plasticity")]
-        if let Some(p) = self.plasticity.as_mut() {
-            p.decay();
-        }
+        self.plasticity.decay();
+        self.inner.compile();
 
         // Pop current slot events (these are the spikes at current time)
-        let events = self.inner.queue.next();
+        let tick = self.inner.queue.current_time;
+        let mut events = self.inner.queue.next();
+        let popped = events.len();
 
-        // Deliver effects and schedule newly fired spikes for their delivery times
-        'events_loop: for ev in &events {
-            let src = ev.neuron_id as usize;
+        // Control events take effect first and are set aside, so delivery, plasticity and
+        // homeostasis only see spikes; they are returned after the spikes
+        let mut control = Vec::new();
+        events.retain(|ev| ev.is_spike() || {
+            control.push(*ev);
+            false
+        });
+        for &ev in &control {
+            self.apply_control(ev);
+        }
 
-            #[cfg(feature = "plasticity")]
-            if let Some(p) = self.plasticity.as_mut() {
-                p.on_pre_spike(ev.neuron_id, ev.time);
+        // Deferred work first: suppressed spikes consume this tick's spike budget and either
+        // go back to the wheel (still in the future) or occur now (late); truncated deliveries
+        // resume ahead of the new events
+        let mut work: Vec<(SpikeEvent, usize, bool)> = Vec::new();
+        if let Some(lateness) = lateness {
+            for mut spike in core::mem::take(&mut self.deferred_spikes) {
+                if !spike_room(spikes_scheduled) {
+                    spike_budget_hit = true;
+                    self.deferred_spikes.push(spike);
+                    continue;
+                }
+                if spike.time > tick {
+                    if self.inner.queue.schedule(spike).is_err() {
+                        dropped += 1;
+                        continue;
+                    }
+                } else if lateness.admit(&mut spike.time, tick) {
+                    events.push(spike);
+                } else {
+                    dropped += 1;
+                    continue;
+                }
+                spikes_scheduled += 1;
+            }
+            for d in core::mem::take(&mut self.deferred_deliveries) {
+                let mut ev = d.event;
+                if lateness.admit(&mut ev.time, tick) {
+                    work.push((ev, d.edge_pos, d.single));
+                } else {
+                    dropped += 1;
+                }
             }
+        }
+        work.extend(events.iter().map(|&ev| (ev, 0, false)));
+
+        // Plasticity sees every spike of this tick, independent of delivery budgets
+        #[cfg(feature = "plasticity")]
+        let plasticity_updates = self.apply_plasticity(&events);
+        #[cfg(not(feature = "plasticity"))]
+        let plasticity_updates = 0;
 
-            let maybe_edges = self.source_to_edges.get(src);
-            if maybe_edges.is_none() {
+        // Edge visits of this tick as (work index, edge position): lazily in work order, or,
+        // once delivery priorities are configured, sorted by descending edge class (stable,
+        // so work order is kept within a class)
+        let edges = Arc::clone(self.inner.shared_edges());
+        let span = |&(ev, start, single): &(SpikeEvent, usize, bool)| {
+            let len = edges.outgoing(ev.neuron_id).len();
+            start..if single { (start + 1).min(len) } else { len }
+        };
+        let edge_of = |(wi, pos): (usize, usize)| edges.outgoing(work[wi].0.neuron_id)[pos];
+        let prioritized = self.prioritized;
+        let mut sorted: Vec<(usize, usize)> = Vec::new();
+        if prioritized {
+            for (wi, item) in work.iter().enumerate() {
+                sorted.extend(span(item).map(|pos| (wi, pos)));
+            }
+            sorted.sort_by_key(|&v| core::cmp::Reverse(edge_class(&self.edge_meta, edge_of(v))));
+        }
+        let mut lazy = work.iter().enumerate().flat_map(|(wi, item)| span(item).map(move |pos| (wi, pos)));
+        let mut sorted_iter = sorted.iter().copied();
+        let mut next_visit = || if prioritized { sorted_iter.next() } else { lazy.next() };
+
+        // Deliver effects and schedule newly fired spikes for their delivery times.
+        // `cut` is the first visit a budget stopped; `starved` marks that later visits are lost
+        let mut cut: Option<(usize, usize)> = None;
+        let mut starved = false;
+        let mut spikes_full = false;
+        'events_loop: while let Some((wi, pos)) = next_visit() {
+            let ev = work[wi].0;
+            let edge = edges.fan_out_at(ev.neuron_id, pos);
+            let eid = edge.id;
+
+            // Budget: edge visits
+            if budgets.max_edge_visits.is_some_and(|max| edge_visits >= max) {
+                edge_budget_hit = true;
+                cut = Some((wi, pos));
+                break 'events_loop;
+            }
+            // Budget: wall-clock deadline
+            if deadline.is_some_and(|d| self.clock.now_micros() >= d) {
+                time_budget_hit = true;
+                cut = Some((wi, pos));
+                break 'events_loop;
+            }
+            edge_visits += 1;
+            class_stats(&mut self.priority_stats, edge_class(&self.edge_meta, eid)).delivered += 1;
+
+            let deliver_time = ev.time.saturating_add(edge.delay);
+            let meta = self.edge_meta.get_mut(eid as usize);
+            let channel = meta.as_ref().and_then(|m| m.channel);
+            // Short-term plasticity scales what this spike delivers
+            let weight = match meta.and_then(|m| m.stp.as_mut()) {
+                Some(stp) => fixed_mul(edge.weight, stp.on_spike(ev.time)),
+                None => edge.weight,
+            };
+
+            // Modulatory edges shift gain/threshold of their targets instead of injecting
+            if let EdgeKind::Modulatory(m) = edge.kind {
+                for &tgt in edge.targets {
+                    self.neurons.modulate(tgt, m, weight);
+                }
                 continue;
             }
-            let edge_ids = maybe_edges.unwrap();
 
-            for &eid in edge_ids {
-                // Budget: edge visits
-                if let Some(max_visits) = budgets.max_edge_visits {
-                    if edge_visits >= max_visits {
-                        break 'events_loop;
-                    }
+            // Kernel synapses feed the target's synaptic state at the arrival tick
+            if let Some(c) = channel {
+                for &tgt in edge.targets {
+                    self.synapses.receive(c, deliver_time, tgt, weight);
                 }
-                edge_visits += 1;
-
-                // In snn-core, id == index
-                if let Some(edge) = self.inner.edges.get(eid as usize) {
-                    let deliver_time = ev.time.saturating_add(edge.delay);
-
-                    for &tgt in &edge.targets {
-                        if let Some(n) = self.inner.neurons.get_mut(tgt as usize) {
-                            let fired = n.inject(edge.weight, deliver_time);
-                            if fired {
-                                let spike = SpikeEvent { neuron_id: tgt, time: deliver_time };
-
-                                // Budget: scheduled spikes
-                                if let Some(max_spikes) = budgets.max_spikes_scheduled {
-                                    if spikes_scheduled >= max_spikes {
-                                        // Do not schedule further spikes this tick
-                                        break 'events_loop;
-                                    }
-                                }
-                                self.inner.queue.schedule(spike);
-                                spikes_scheduled += 1;
-
-                                #[cfg(feature = "plasticity")]
-                                if let Some(p) = self.plasticity.as_mut() {
-                                    p.on_post_spike(tgt, deliver_time);
-                                    // Allow rule to update weight
-                                    // SAFETY: we have &mut self, then immediate second borrow by index
-                                    if let Some(edge_mut) = self.inner.edges.get_mut(eid as usize) {
-                                        p.apply_edge(ev.neuron_id, tgt, &mut edge_mut.weight);
-                                    }
-                                }
+                injected += edge.targets.len();
+                continue;
+            }
+
+            if two_phase {
+                for &tgt in edge.targets {
+                    self.accumulator.add(deliver_time, tgt, weight);
+                }
+                injected += edge.targets.len();
+                continue;
+            }
+
+            for &tgt in edge.targets {
+                if self.neurons.contains(tgt) {
+                    injected += 1;
+                    let fired = self.neurons.inject(tgt, weight, deliver_time);
+                    if fired {
+                        let spike = SpikeEvent::spike(tgt, deliver_time).with_offset(ev.offset);
+
+                        // Late work (deferred, kept timestamps) can fire into a past
+                        // tick; such spikes occur on the next tick instead
+                        if lateness.is_some() && deliver_time <= tick {
+                            self.deferred_spikes.push(spike);
+                            continue;
+                        }
+
+                        // Budget: scheduled spikes
+                        if !spike_room(spikes_scheduled) {
+                            spike_budget_hit = true;
+                            if lateness.is_none() {
+                                // Do not schedule further spikes this tick; the
+                                // spike and the rest of the tick's deliveries are lost
+                                dropped += 1;
+                                starved = true;
+                                break 'events_loop;
                             }
+                            // Finish this edge, deferring its spikes, then stop
+                            self.deferred_spikes.push(spike);
+                            spikes_full = true;
+                            continue;
+                        }
+                        // Zero-delay spikes occur on the next tick; beyond-horizon ones are lost
+                        match self.inner.queue.schedule_clamped(spike) {
+                            Ok(_) => spikes_scheduled += 1,
+                            Err(_) => dropped += 1,
+                        }
+                    }
+                }
+            }
+            if spikes_full {
+                starved = true;
+                break 'events_loop;
+            }
+        }
+
+        // Visits that did not run: count them per class, then defer or drop them
+        let mut rest: Vec<(usize, usize)> = cut.into_iter().collect();
+        if cut.is_some() || starved {
+            rest.extend(core::iter::from_fn(&mut next_visit));
+        }
+        for &v in &rest {
+            let stat = class_stats(&mut self.priority_stats, edge_class(&self.edge_meta, edge_of(v)));
+            if lateness.is_some() {
+                stat.deferred += 1;
+            } else {
+                stat.dropped += 1;
+            }
+        }
+        if lateness.is_none() {
+            dropped += rest.len();
+        } else {
+            // In work order the rest of each work item is one contiguous tail; sorted visits
+            // are deferred one edge at a time
+            let mut last = None;
+            for &(wi, pos) in &rest {
+                let (event, _, single) = work[wi];
+                if prioritized {
+                    self.deferred_deliveries.push(DeferredDelivery { event, edge_pos: pos, single: true });
+                } else if last != Some(wi) {
+                    self.deferred_deliveries.push(DeferredDelivery { event, edge_pos: pos, single });
+                }
+                last = Some(wi);
+            }
+        }
+
+        // Release the shared handle so later edge updates this tick do not copy the arena
+        drop(edges);
+
+        // Two-phase: threshold once per target on the summed input, in (time, id) order
+        if two_phase && !self.accumulator.is_empty() {
+            for (time, tgt, input) in self.accumulator.drain_sorted() {
+                if self.neurons.inject(tgt, input, time) {
+                    let spike = SpikeEvent::spike(tgt, time);
+                    if lateness.is_some() && time <= tick {
+                        self.deferred_spikes.push(spike);
+                        continue;
+                    }
+                    if !spike_room(spikes_scheduled) {
+                        spike_budget_hit = true;
+                        if lateness.is_none() {
+                            dropped += 1;
+                            break;
                         }
+                        self.deferred_spikes.push(spike);
+                        continue;
                     }
+                    match self.inner.queue.schedule_clamped(spike) {
+                        Ok(_) => spikes_scheduled += 1,
+                        Err(_) => dropped += 1,
+                    }
+                }
+            }
+        }
+
+        // Integrate synaptic kernels for this tick; crossings spike on the next tick, at the
+        // interpolated offset of the crossing
+        let mut fired = Vec::new();
+        self.synapses.step(tick, &mut self.neurons, &mut fired);
+        let kernel_fired = fired.len();
+        for (i, (n, offset)) in fired.into_iter().enumerate() {
+            let spike = SpikeEvent::spike(n, tick.saturating_add(1)).with_offset(offset);
+            if !spike_room(spikes_scheduled) {
+                spike_budget_hit = true;
+                if lateness.is_none() {
+                    dropped += kernel_fired - i;
+                    break;
                 }
+                self.deferred_spikes.push(spike);
+                continue;
+            }
+            match self.inner.queue.schedule_clamped(spike) {
+                Ok(_) => spikes_scheduled += 1,
+                Err(_) => dropped += 1,
             }
         }
 
-        events
+        #[cfg(feature = "plasticity")]
+        self.apply_homeostasis(&events);
+        #[cfg(feature = "plasticity")]
+        self.apply_structural(&events);
+
+        events.extend(control);
+        let (deferred_deliveries, deferred_spikes) = self.deferred_counts();
+        let report = StepReport {
+            tick,
+            time_ms: self.inner.time_base().ticks_to_ms(tick),
+            spikes: events,
+            popped,
+            edges_visited: edge_visits,
+            targets_injected: injected,
+            spikes_scheduled,
+            deferred: deferred_deliveries + deferred_spikes,
+            dropped,
+            edge_budget_hit,
+            spike_budget_hit,
+            time_budget_hit,
+            elapsed_micros: self.clock.now_micros().saturating_sub(start),
+            plasticity_updates,
+        };
+        if let Some(a) = self.adaptive.as_mut() {
+            a.observe(&report);
+        }
+        report
     }
 
     /// Advance one tick without budgets; returns the spikes that occurred this tick.
@@ -192,16 +1194,978 @@ impl SnnRuntimePlus {
         self.step_once_with_budgets(StepBudgets::default())
     }
 
-    /// Run until the given tick (inclusive), ignoring budgets.
+    /// First tick in `current_time..=until + 1` that has to be stepped: the next wheel event,
+    /// kernel arrival, homeostatic or structural update. Ticks before it change nothing but
+    /// plasticity trace decay.
+    fn quiescent_until(&self, until: u64) -> u64 {
+        let now = self.inner.queue.current_time;
+        let (deliveries, spikes) = self.deferred_counts();
+        if deliveries + spikes > 0 {
+            return now;
+        }
+        let mut stop = until.saturating_add(1);
+        if let Some(t) = self.inner.queue.next_event_time() {
+            stop = stop.min(t);
+        }
+        match self.synapses.next_activity() {
+            None => return now,
+            Some(Some(t)) => stop = stop.min(t),
+            Some(None) => {}
+        }
+        // Periodic updates run after the tick t with (t + 1) % period == 0
+        let boundary = |period: u64| now.saturating_add(1).next_multiple_of(period.max(1)) - 1;
+        #[cfg(feature = "plasticity")]
+        {
+            for period in self.plasticity.homeostatic_periods() {
+                stop = stop.min(boundary(period));
+            }
+            if let (Some(engine), false) = (&self.structural, self.plasticity.is_frozen()) {
+                stop = stop.min(boundary(engine.config().period));
+            }
+        }
+        #[cfg(not(feature = "plasticity"))]
+        let _ = boundary;
+        stop.max(now)
+    }
+
+    /// Skip quiet ticks up to (at most) `until`, decaying plasticity traces over the skipped
+    /// interval in closed form. Returns the number of ticks skipped. The result matches
+    /// stepping them except for trace rounding, bounded in `PlasticityRule::decay_ticks`.
+    pub fn fast_forward(&mut self, until: u64) -> u64 {
+        let now = self.inner.queue.current_time;
+        let stop = self.quiescent_until(until).min(until.saturating_add(1));
+        if stop <= now {
+            return 0;
+        }
+        #[cfg(feature = "plasticity")]
+        self.plasticity.decay_ticks(stop - now);
+        self.inner.queue.skip_to(stop);
+        stop - now
+    }
+
+    /// Run until the given tick (inclusive), ignoring budgets. Quiet stretches are skipped
+    /// with `fast_forward`.
     pub fn run_until(&mut self, until: u64) {
         while self.inner.queue.current_time <= until {
-            let _ = self.step_once();
+            if self.fast_forward(until) == 0 {
+                let events = self.step_once();
+                self.inner.queue.recycle(events);
+            }
         }
     }
 
+    /// Step tick by tick (without budgets) until one of `conditions` holds and report which
+    /// one did; see `SnnRuntime::run_until_condition`. Quiescence here also requires no
+    /// deferred work and idle synapse kernels.
+    pub fn run_until_condition(&mut self, conditions: &[StopCondition]) -> RunSummary {
+        let mut tracker = StopTracker::new(conditions);
+        let mut events = Vec::new();
+        loop {
+            if let Some(index) = tracker.check(&events, self.is_quiescent()) {
+                self.inner.queue.recycle(events);
+                return tracker.summary(index);
+            }
+            let tick = self.inner.queue.current_time;
+            let next = self.step_once();
+            tracker.record_tick(tick, &next);
+            self.inner.queue.recycle(core::mem::replace(&mut events, next));
+        }
+    }
+
+    // Nothing scheduled, deferred or still decaying in a synapse kernel
+    fn is_quiescent(&self) -> bool {
+        self.inner.queue.is_empty() && self.deferred_counts() == (0, 0) && self.synapses.next_activity() == Some(None)
+    }
+
     /// Run a fixed number of ticks, ignoring budgets.
     pub fn run_ticks(&mut self, ticks: u64) {
         let until = self.inner.queue.current_time.saturating_add(ticks);
         self.run_until(until);
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reverse_adjacency_matches_edges() {
+        let mut rt = SnnRuntimePlus::new(8);
+        let a = rt.add_neuron(1.0);
+        let b = rt.add_neuron(1.0);
+        let c = rt.add_neuron(1.0);
+        rt.add_edge(vec![a], vec![b, c], 0.5, 1);
+        rt.add_edge(vec![b], vec![c], 0.5, 1);
+        rt.inner.compile();
+        assert_eq!(rt.edges().incoming(c), &[0, 1]);
+
+        let rebuilt = SnnRuntimePlus::from_inner(rt.into_inner());
+        assert_eq!(rebuilt.edges().incoming(b), &[0]);
+        assert_eq!(rebuilt.edges().incoming(c), &[0, 1]);
+    }
+
+    #[cfg(feature = "plasticity")]
+    #[test]
+    fn test_pre_after_post_depresses() {
+        use crate::plasticity::{PairStdp, StdpPairing};
+        let mut rt = SnnRuntimePlus::new(16);
+        let pre = rt.add_neuron(1.0);
+        let post = rt.add_neuron(1.0);
+        rt.add_edge(vec![pre], vec![post], 0.5, 1);
+        rt.set_plasticity(PairStdp::new(StdpPairing::AllToAll, 0.1, 0.1, 20.0, 20.0, 0.0, 2.0));
+
+        rt.queue().schedule(SpikeEvent::spike(post, 0)).unwrap();
+        rt.queue().schedule(SpikeEvent::spike(pre, 5)).unwrap();
+        rt.run_until(6);
+        assert!(rt.edges().weight(0) < snn_core::to_fixed(0.5));
+    }
+
+    #[cfg(feature = "plasticity")]
+    #[test]
+    fn test_post_spike_from_other_edge_potentiates() {
+        use crate::plasticity::{PairStdp, StdpPairing};
+        let mut rt = SnnRuntimePlus::new(16);
+        let weak = rt.add_neuron(1.0);
+        let strong = rt.add_neuron(1.0);
+        let post = rt.add_neuron(1.0);
+        rt.add_edge(vec![weak], vec![post], 0.25, 1);
+        rt.add_edge(vec![strong], vec![post], 1.0, 1);
+        rt.set_plasticity(PairStdp::new(StdpPairing::AllToAll, 0.1, 0.1, 20.0, 20.0, 0.0, 2.0));
+
+        rt.queue().schedule(SpikeEvent::spike(weak, 0)).unwrap();
+        rt.queue().schedule(SpikeEvent::spike(strong, 3)).unwrap();
+        // post fires at t=4 via the strong edge; the weak edge is potentiated as well
+        rt.run_until(5);
+        assert!(rt.edges().weight(0) > snn_core::to_fixed(0.25));
+        assert!(rt.edges().weight(1) > snn_core::to_fixed(1.0));
+    }
+
+    #[cfg(feature = "plasticity")]
+    #[test]
+    fn test_structural_prune_keeps_adjacency_consistent() {
+        use crate::structural::{StructuralConfig, StructuralEvent};
+        let mut rt = SnnRuntimePlus::new(8);
+        for _ in 0..3 {
+            rt.add_neuron(1.0);
+        }
+        rt.add_edge(vec![0], vec![1], 0.001, 1);
+        rt.add_edge(vec![1], vec![2], 0.5, 1);
+        rt.set_structural_plasticity(StructuralConfig {
+            period: 2,
+            prune_threshold: 0.01,
+            prune_after: 2,
+            grow_per_update: 0,
+            ..Default::default()
+        });
+        rt.run_ticks(5);
+
+        let events = rt.take_structural_events();
+        assert!(matches!(events[..], [StructuralEvent::Pruned { edge_id: 0, .. }]));
+        assert_eq!(rt.edges().len(), 1);
+        assert_eq!(rt.edges().edge(0).id, 0);
+        assert_eq!(rt.edges().outgoing(1), &[0]);
+        assert!(rt.edges().outgoing(0).is_empty());
+        assert_eq!(rt.edges().incoming(2), &[0]);
+    }
+
+    #[cfg(feature = "plasticity")]
+    #[test]
+    fn test_remove_edges_renumbers_edge_scopes() {
+        use crate::plasticity::QuantizedStdp;
+        use crate::plasticity_stack::RuleScope;
+        let mut rt = SnnRuntimePlus::new(8);
+        for _ in 0..3 {
+            rt.add_neuron(1.0);
+        }
+        for (s, t) in [(0, 1), (1, 2), (2, 0)] {
+            rt.add_edge(vec![s], vec![t], 0.5, 1);
+        }
+        rt.add_plasticity("scoped", QuantizedStdp::with_defaults(), RuleScope::edges(vec![1, 2]));
+        rt.remove_edges(&[1]);
+        // Old edge 2 (2 -> 0) is now edge 1
+        assert_eq!(rt.plasticity().get("scoped").unwrap().scope, RuleScope::edges(vec![1]));
+        assert_eq!(rt.edges().sources(1), &[2]);
+    }
+
+    #[cfg(feature = "plasticity")]
+    #[test]
+    fn test_remove_edges_renumbers_pruning_clocks() {
+        use crate::structural::StructuralConfig;
+        let mut rt = SnnRuntimePlus::new(8);
+        for _ in 0..3 {
+            rt.add_neuron(1.0);
+        }
+        rt.add_edge(vec![0], vec![1], 0.05, 1);
+        rt.add_edge(vec![1], vec![2], 0.5, 1);
+        rt.set_structural_plasticity(StructuralConfig { period: 10, prune_threshold: 0.1, prune_after: 20, ..Default::default() });
+        // Edge 0 is weak from the update at tick 10 on
+        rt.run_until(10);
+
+        // Removing it externally must not hand its clock to old edge 1, weak only from 20
+        rt.remove_edges(&[0]);
+        rt.edges_mut().weights_mut()[0] = to_fixed(0.05);
+        rt.run_until(30);
+        assert_eq!(rt.edges().len(), 1);
+        rt.run_until(40);
+        assert!(rt.edges().is_empty());
+    }
+
+    #[cfg(feature = "plasticity")]
+    #[test]
+    fn test_grown_edges_follow_sources_typed_sign_under_dales_law() {
+        use crate::structural::{StructuralConfig, StructuralEvent};
+        use snn_core::Modulation;
+        let mut rt = SnnRuntimePlus::new(8);
+        for _ in 0..4 {
+            rt.add_neuron(10.0);
+        }
+        rt.set_dales_law(true);
+        // Neuron 0's first edge is modulatory; its sign comes from the inhibitory one
+        let gain = EdgeOptions { kind: Some(EdgeKind::Modulatory(Modulation::Gain)), ..Default::default() };
+        rt.add_edge_with(vec![0], vec![1], 0.0, 1, gain);
+        rt.add_edge(vec![0], vec![2], -0.5, 1);
+        rt.set_structural_plasticity(StructuralConfig {
+            period: 2,
+            prune_threshold: 0.0,
+            grow_per_update: 2,
+            ..Default::default()
+        });
+        rt.queue().schedule(SpikeEvent::spike(0, 0)).unwrap();
+        rt.queue().schedule(SpikeEvent::spike(3, 0)).unwrap();
+        rt.run_ticks(3);
+
+        let grown: Vec<(NeuronId, u32)> = rt
+            .take_structural_events()
+            .into_iter()
+            .filter_map(|e| match e {
+                StructuralEvent::Grown { source, edge_id, .. } => Some((source, edge_id)),
+                _ => None,
+            })
+            .collect();
+        assert!(grown.iter().any(|&(source, _)| source == 0));
+        for (source, id) in grown {
+            let want = if source == 0 { EdgeKind::Inhibitory } else { EdgeKind::Excitatory };
+            assert_eq!(rt.edges().kind(id), want);
+        }
+        assert_eq!(rt.validate(), Ok(()));
+    }
+
+    #[cfg(feature = "plasticity")]
+    #[test]
+    fn test_frozen_and_disabled_rules_leave_weights() {
+        use crate::plasticity::{PairStdp, StdpPairing};
+        let run = |setup: &dyn Fn(&mut SnnRuntimePlus)| {
+            let mut rt = SnnRuntimePlus::new(16);
+            let pre = rt.add_neuron(1.0);
+            let post = rt.add_neuron(1.0);
+            rt.add_edge(vec![pre], vec![post], 0.5, 1);
+            rt.set_plasticity(PairStdp::new(StdpPairing::AllToAll, 0.1, 0.1, 20.0, 20.0, 0.0, 2.0));
+            setup(&mut rt);
+            rt.queue().schedule(SpikeEvent::spike(post, 0)).unwrap();
+            rt.queue().schedule(SpikeEvent::spike(pre, 5)).unwrap();
+            rt.run_until(6);
+            rt.edges().weight(0)
+        };
+        let initial = snn_core::to_fixed(0.5);
+        assert!(run(&|_| {}) < initial);
+        assert_eq!(run(&|rt| rt.freeze_weights(true)), initial);
+        assert_eq!(run(&|rt| assert!(rt.plasticity_mut().set_enabled("default", false))), initial);
+        assert_eq!(run(&|rt| assert!(rt.remove_plasticity("default"))), initial);
+    }
+
+    #[test]
+    fn test_stp_edge_depresses_delivered_weight() {
+        let mut rt = SnnRuntimePlus::new(8);
+        let pre = rt.add_neuron(1.0);
+        let post = rt.add_neuron(100.0);
+        let opts = EdgeOptions { stp: Some(StpParams::depressing(0.5, 1000.0)), ..Default::default() };
+        let eid = rt.add_edge_with(vec![pre], vec![post], 1.0, 1, opts);
+
+        rt.queue().schedule(SpikeEvent::spike(pre, 0)).unwrap();
+        rt.step_once();
+        let first = rt.neurons().membrane[post as usize];
+        rt.queue().schedule(SpikeEvent::spike(pre, 1)).unwrap();
+        rt.step_once();
+        let second = rt.neurons().membrane[post as usize] - first;
+
+        assert!((snn_core::from_fixed(first) - 0.5).abs() < 1e-3);
+        assert!(second < first);
+        assert!(rt.stp_state(eid).is_some());
+    }
+
+    #[test]
+    fn test_modulatory_edge_scales_gain_without_firing() {
+        use snn_core::Modulation;
+        let mut rt = SnnRuntimePlus::new(8);
+        let m = rt.add_neuron(1.0);
+        let pre = rt.add_neuron(1.0);
+        let post = rt.add_neuron(1.0);
+        let opts = EdgeOptions { kind: Some(EdgeKind::Modulatory(Modulation::Gain)), ..Default::default() };
+        rt.add_edge_with(vec![m], vec![post], 1.0, 1, opts);
+        rt.add_edge(vec![pre], vec![post], 0.5, 1);
+
+        rt.queue().schedule(SpikeEvent::spike(m, 0)).unwrap();
+        rt.queue().schedule(SpikeEvent::spike(pre, 1)).unwrap();
+        let mut fired = false;
+        for _ in 0..4 {
+            fired |= rt.step_once().iter().any(|s| s.neuron_id == post);
+        }
+        // Gain doubled, so 0.5 input reaches threshold 1.0
+        assert_eq!(rt.neurons().gain[post as usize], snn_core::SCALE * 2);
+        assert!(fired);
+    }
+
+    #[test]
+    fn test_validate_dales_law() {
+        let mut rt = SnnRuntimePlus::new(8);
+        for _ in 0..3 {
+            rt.add_neuron(1.0);
+        }
+        rt.add_edge(vec![0], vec![1], 0.5, 1);
+        rt.add_edge(vec![0], vec![2], -0.5, 1);
+        assert_eq!(rt.validate(), Ok(()));
+        rt.set_dales_law(true);
+        assert_eq!(rt.validate(), Err(EdgeError::DaleViolation { neuron: 0, edge: 1 }));
+
+        let opts = EdgeOptions { kind: Some(EdgeKind::Excitatory), ..Default::default() };
+        let bad = rt.add_edge_with(vec![1], vec![2], -0.5, 1, opts);
+        rt.set_dales_law(false);
+        assert_eq!(rt.validate(), Err(EdgeError::SignMismatch { edge: bad }));
+    }
+
+    #[cfg(feature = "plasticity")]
+    #[test]
+    fn test_dales_law_clamps_learned_weights() {
+        use crate::plasticity::{PairStdp, StdpPairing};
+        let run = |dale: bool| {
+            let mut rt = SnnRuntimePlus::new(16);
+            let pre = rt.add_neuron(1.0);
+            let post = rt.add_neuron(1.0);
+            rt.add_edge(vec![pre], vec![post], 0.05, 1);
+            rt.set_plasticity(PairStdp::new(StdpPairing::AllToAll, 0.1, 0.5, 20.0, 20.0, -1.0, 1.0));
+            rt.set_dales_law(dale);
+            rt.queue().schedule(SpikeEvent::spike(post, 0)).unwrap();
+            rt.queue().schedule(SpikeEvent::spike(pre, 1)).unwrap();
+            rt.run_until(2);
+            rt.edges().weight(0)
+        };
+        assert!(run(false) < 0);
+        assert_eq!(run(true), 0);
+    }
+
+    #[test]
+    fn test_two_phase_is_independent_of_edge_order() {
+        let run = |mode: StepMode, weights: [f32; 2]| {
+            let mut rt = SnnRuntimePlus::new(8);
+            let pre = rt.add_neuron(1.0);
+            let post = rt.add_neuron(1.0);
+            rt.set_step_mode(mode);
+            for w in weights {
+                rt.add_edge(vec![pre], vec![post], w, 1);
+            }
+            rt.queue().schedule(SpikeEvent::spike(pre, 0)).unwrap();
+            rt.step_once();
+            (rt.step_once().len(), rt.neurons().membrane[post as usize])
+        };
+        // Immediate delivery fires or not depending on which edge comes first
+        assert_ne!(run(StepMode::Immediate, [1.0, -0.5]), run(StepMode::Immediate, [-0.5, 1.0]));
+        let a = run(StepMode::TwoPhase, [1.0, -0.5]);
+        assert_eq!(a, run(StepMode::TwoPhase, [-0.5, 1.0]));
+        assert_eq!(a, (0, snn_core::to_fixed(0.5)));
+    }
+
+    #[test]
+    fn test_budget_truncation_follows_event_order() {
+        use snn_core::EventOrder;
+        let run = |order: EventOrder| {
+            let mut rt = SnnRuntimePlus::new(8);
+            for _ in 0..4 {
+                rt.add_neuron(10.0);
+            }
+            rt.add_edge(vec![0], vec![3], 1.0, 1);
+            rt.add_edge(vec![1], vec![3], 2.0, 1);
+            rt.add_edge(vec![2], vec![3], 4.0, 1);
+            rt.queue().set_order(order);
+            rt.queue().set_priority(2, 1);
+            for n in [1, 2, 0] {
+                rt.queue().schedule(SpikeEvent::spike(n, 0)).unwrap();
+            }
+            let budgets = StepBudgets { max_edge_visits: Some(1), ..Default::default() };
+            let popped: Vec<NeuronId> = rt.step_once_with_budgets(budgets).iter().map(|e| e.neuron_id).collect();
+            (popped, snn_core::from_fixed(rt.neurons().membrane[3]))
+        };
+        assert_eq!(run(EventOrder::Fifo), (vec![1, 2, 0], 2.0));
+        assert_eq!(run(EventOrder::NeuronId), (vec![0, 1, 2], 1.0));
+        assert_eq!(run(EventOrder::Priority), (vec![2, 0, 1], 4.0));
+    }
+
+    #[test]
+    fn test_deferred_work_resumes_next_tick() {
+        let run = |policy: OverflowPolicy| {
+            let mut rt = SnnRuntimePlus::new(16);
+            let src = rt.add_neuron(1.0);
+            for _ in 0..3 {
+                let t = rt.add_neuron(1.0);
+                rt.add_edge(vec![src], vec![t], 1.0, 2);
+            }
+            rt.set_overflow_policy(policy);
+            rt.queue().schedule(SpikeEvent::spike(src, 0)).unwrap();
+            let budgets = StepBudgets { max_edge_visits: Some(1), ..Default::default() };
+            let mut spikes = Vec::new();
+            for _ in 0..6 {
+                spikes.extend(rt.step_once_with_budgets(budgets).into_iter().map(|s| (s.neuron_id, s.time)));
+            }
+            (spikes, rt.deferred_counts())
+        };
+        assert_eq!(run(OverflowPolicy::Drop).0, vec![(0, 0), (1, 2)]);
+        // Kept timestamps: the third delivery runs late (tick 2) and its spike occurs at tick 3
+        let (kept, pending) = run(OverflowPolicy::Defer(Lateness::Keep));
+        assert_eq!(kept, vec![(0, 0), (1, 2), (2, 2), (3, 2)]);
+        assert_eq!(pending, (0, 0));
+        let (restamped, _) = run(OverflowPolicy::Defer(Lateness::Restamp));
+        assert_eq!(restamped, vec![(0, 0), (1, 2), (2, 3), (3, 4)]);
+        let (bounded, _) = run(OverflowPolicy::Defer(Lateness::DropAfter(1)));
+        assert_eq!(bounded, vec![(0, 0), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_remove_edges_repoints_deferred_deliveries() {
+        let mut rt = SnnRuntimePlus::new(16);
+        let src = rt.add_neuron(1.0);
+        for _ in 0..4 {
+            let t = rt.add_neuron(1.0);
+            rt.add_edge(vec![src], vec![t], 1.0, 2);
+        }
+        rt.set_overflow_policy(OverflowPolicy::Defer(Lateness::Keep));
+        rt.queue().schedule(SpikeEvent::spike(src, 0)).unwrap();
+        let budgets = StepBudgets { max_edge_visits: Some(1), ..Default::default() };
+        let mut spikes: Vec<(NeuronId, u64)> = rt.step_once_with_budgets(budgets).iter().map(|s| (s.neuron_id, s.time)).collect();
+        assert_eq!(rt.deferred_counts(), (1, 0));
+
+        // Edge 0 already delivered; the deferred tail must still cover the other three
+        rt.remove_edges(&[0]);
+        for _ in 0..3 {
+            spikes.extend(rt.step_once().iter().map(|s| (s.neuron_id, s.time)));
+        }
+        assert_eq!(spikes, vec![(0, 0), (1, 2), (2, 2), (3, 2), (4, 2)]);
+    }
+
+    #[test]
+    fn test_step_report_counts_and_budget_flags() {
+        let mut rt = SnnRuntimePlus::new(16);
+        let src = rt.add_neuron(1.0);
+        for _ in 0..3 {
+            let t = rt.add_neuron(1.0);
+            rt.add_edge(vec![src], vec![t], 1.0, 1);
+        }
+        rt.queue().schedule(SpikeEvent::spike(src, 0)).unwrap();
+        let report = rt.step_with_report(StepBudgets { max_edge_visits: Some(2), ..Default::default() });
+        assert_eq!(report.tick, 0);
+        assert_eq!((report.popped, report.edges_visited, report.targets_injected), (1, 2, 2));
+        assert_eq!((report.spikes_scheduled, report.dropped, report.deferred), (2, 1, 0));
+        assert!(report.edge_budget_hit && !report.spike_budget_hit);
+
+        let report = rt.step_with_report(StepBudgets { max_spikes_scheduled: Some(0), ..Default::default() });
+        assert_eq!((report.tick, report.spikes.len()), (1, 2));
+        assert!(!report.budget_hit());
+    }
+
+    #[test]
+    fn test_deadline_stops_delivery() {
+        use crate::deadline::MockClock;
+        let mut rt = SnnRuntimePlus::new(16);
+        let src = rt.add_neuron(1.0);
+        for _ in 0..4 {
+            let t = rt.add_neuron(1.0);
+            rt.add_edge(vec![src], vec![t], 1.0, 1);
+        }
+        // Every clock read costs 10us: start, then one check per edge visit
+        rt.set_clock(MockClock::with_auto_advance(10));
+        rt.set_overflow_policy(OverflowPolicy::Defer(Lateness::Keep));
+        rt.queue().schedule(SpikeEvent::spike(src, 0)).unwrap();
+        let report = rt.step_with_report(StepBudgets { max_micros: Some(25), ..Default::default() });
+        assert!(report.time_budget_hit && !report.edge_budget_hit);
+        assert_eq!(report.edges_visited, 2);
+        assert_eq!(rt.deferred_counts(), (1, 0));
+    }
+
+    #[test]
+    fn test_adaptive_budget_caps_edge_visits() {
+        use crate::deadline::{AdaptiveBudget, MockClock};
+        let mut rt = SnnRuntimePlus::new(16);
+        let src = rt.add_neuron(1.0);
+        for _ in 0..8 {
+            let t = rt.add_neuron(10.0);
+            rt.add_edge(vec![src], vec![t], 1.0, 1);
+        }
+        // Each tick reads the clock twice, so every tick "takes" 400us against a 100us target
+        rt.set_clock(MockClock::with_auto_advance(400));
+        rt.set_adaptive_budget(Some(AdaptiveBudget::new(100, 1, 8)));
+        let mut visited = Vec::new();
+        for t in 0..3 {
+            rt.queue().schedule(SpikeEvent::spike(src, t)).unwrap();
+            visited.push(rt.step_with_report(StepBudgets::default()).edges_visited);
+        }
+        assert_eq!(visited, vec![8, 2, 1]);
+        let stats = rt.adaptive_budget().unwrap().stats();
+        assert_eq!((stats.overrun_ticks, stats.limited_ticks, stats.shed_work), (3, 2, 13));
+    }
+
+    #[test]
+    fn test_priority_classes_are_served_first() {
+        let mut rt = SnnRuntimePlus::new(16);
+        let src = rt.add_neuron(1.0);
+        let targets: Vec<NeuronId> = (0..4).map(|_| rt.add_neuron(10.0)).collect();
+        for &t in &targets {
+            rt.add_edge(vec![src], vec![t], 1.0, 1);
+        }
+        // Output neuron 4 via its neuron class, edge 1 explicitly
+        rt.set_neuron_priority(targets[3], 2);
+        assert_eq!(rt.queue().priority(targets[3]), 2);
+        rt.set_edge_priority(1, 1);
+        assert_eq!(rt.edge_priority(3), 2);
+        rt.set_overflow_policy(OverflowPolicy::Defer(Lateness::Keep));
+
+        let budgets = StepBudgets { max_edge_visits: Some(2), ..Default::default() };
+        rt.queue().schedule(SpikeEvent::spike(src, 0)).unwrap();
+        rt.step_with_report(budgets);
+        let fed: Vec<bool> = targets.iter().map(|&t| rt.neurons().membrane[t as usize] > 0).collect();
+        assert_eq!(fed, vec![false, true, false, true]);
+        assert_eq!(rt.deferred_counts(), (2, 0));
+
+        let stats = rt.priority_stats();
+        assert_eq!(stats[0], ClassStats { delivered: 0, deferred: 2, dropped: 0 });
+        assert_eq!(stats[1].delivered + stats[2].delivered, 2);
+
+        // Deferred low-priority work completes on the next tick
+        rt.step_with_report(budgets);
+        assert!(targets.iter().all(|&t| rt.neurons().membrane[t as usize] > 0));
+        assert_eq!(rt.priority_stats()[0].delivered, 2);
+    }
+
+    #[cfg(feature = "plasticity")]
+    #[test]
+    fn test_fast_forward_matches_stepping() {
+        use crate::homeostasis::IntrinsicPlasticity;
+        use crate::plasticity::QuantizedStdp;
+        let build = || {
+            let mut rt = SnnRuntimePlus::new(8192);
+            let a = rt.add_neuron(1.0);
+            let b = rt.add_neuron(1.0);
+            let c = rt.add_neuron(1.5);
+            rt.add_edge(vec![a], vec![b], 1.0, 3);
+            let opts = EdgeOptions { synapse: SynapseKind::ExpCurrent { tau: 3.0 }, ..Default::default() };
+            rt.add_edge_with(vec![b], vec![c], 1.0, 2, opts);
+            rt.set_plasticity(QuantizedStdp::new(0.1, 0.1, 0.9, 0.9, 0.0, 2.0));
+            rt.add_homeostasis("ip", IntrinsicPlasticity::new(500, 0.001, 0.5, 0.5, 2.0));
+            for t in [0, 40, 1_000, 5_000] {
+                rt.queue().schedule(SpikeEvent::spike(a, t)).unwrap();
+            }
+            rt
+        };
+        let mut fast = build();
+        let mut slow = build();
+        fast.run_until(6_000);
+        while slow.inner.queue.current_time <= 6_000 {
+            slow.step_once();
+        }
+        assert_eq!(fast.inner.queue.current_time, slow.inner.queue.current_time);
+        // Closed-form trace decay rounds differently from per-tick decay (see
+        // `PlasticityRule::decay_ticks`); learned weights agree to a few units of 2^-16
+        for (x, y) in fast.edges().iter().zip(slow.edges().iter()) {
+            assert!((x.weight - y.weight).abs() <= 4, "{} vs {}", x.weight, y.weight);
+        }
+        for (x, y) in fast.neurons().iter().zip(slow.neurons().iter()) {
+            assert_eq!((x.membrane, x.threshold), (y.membrane, y.threshold));
+        }
+    }
+
+    #[test]
+    fn test_fast_forward_stops_at_next_event() {
+        let mut rt = SnnRuntimePlus::new(32);
+        let a = rt.add_neuron(1.0);
+        rt.queue().schedule(SpikeEvent::spike(a, 20)).unwrap();
+        assert_eq!(rt.queue().next_event_time(), Some(20));
+        assert_eq!(rt.fast_forward(100), 20);
+        assert_eq!(rt.step_once().len(), 1);
+        assert_eq!(rt.fast_forward(100), 80);
+        assert_eq!(rt.inner.queue.current_time, 101);
+    }
+
+    #[test]
+    fn test_suppressed_spikes_are_deferred() {
+        let mut rt = SnnRuntimePlus::new(16);
+        let src = rt.add_neuron(1.0);
+        let a = rt.add_neuron(1.0);
+        let b = rt.add_neuron(1.0);
+        rt.add_edge(vec![src], vec![a, b], 1.0, 1);
+        rt.set_overflow_policy(OverflowPolicy::Defer(Lateness::Keep));
+        rt.queue().schedule(SpikeEvent::spike(src, 0)).unwrap();
+        let budgets = StepBudgets { max_spikes_scheduled: Some(1), ..Default::default() };
+        rt.step_once_with_budgets(budgets);
+        assert_eq!(rt.deferred_counts(), (0, 1));
+        let at_one: Vec<NeuronId> = rt.step_once_with_budgets(budgets).iter().map(|s| s.neuron_id).collect();
+        assert_eq!(at_one, vec![a, b]);
+    }
+
+    #[test]
+    fn test_exp_current_edge_integrates_over_ticks() {
+        let mut rt = SnnRuntimePlus::new(16);
+        let pre = rt.add_neuron(1.0);
+        let post = rt.add_neuron(1.5);
+        let opts = EdgeOptions { synapse: SynapseKind::ExpCurrent { tau: 4.0 }, ..Default::default() };
+        rt.add_edge_with(vec![pre], vec![post], 1.0, 1, opts);
+
+        rt.queue().schedule(SpikeEvent::spike(pre, 0)).unwrap();
+        let mut fired_at = None;
+        for _ in 0..8 {
+            let t = rt.inner.queue.current_time;
+            if rt.step_once().iter().any(|s| s.neuron_id == post) {
+                fired_at = Some(t);
+                break;
+            }
+        }
+        // Input arrives at t=1 (1.0), reaches 1.5 during t=2, spike lands on t=3
+        assert_eq!(fired_at, Some(3));
+    }
+
+    #[test]
+    fn test_inhibitory_conductance_edge_hyperpolarizes() {
+        let mut rt = SnnRuntimePlus::new(16);
+        let pre = rt.add_neuron(1.0);
+        let post = rt.add_neuron(1.0);
+        rt.neurons_mut().membrane_mut()[post as usize] = to_fixed(0.5);
+        let opts = EdgeOptions { synapse: SynapseKind::Conductance { tau: 4.0, e_rev: -1.0 }, ..Default::default() };
+        rt.add_edge_with(vec![pre], vec![post], -0.5, 1, opts);
+        assert_eq!(rt.edges().kind(0), EdgeKind::Inhibitory);
+
+        rt.queue().schedule(SpikeEvent::spike(pre, 0)).unwrap();
+        rt.run_until(10);
+        // The negative weight opens the channel; the membrane moves toward E_rev, not away
+        let v = rt.neurons().membrane[post as usize];
+        assert!(v < to_fixed(0.5) && v > to_fixed(-1.0), "membrane {v}");
+    }
+
+    #[test]
+    fn test_zero_delay_fires_next_tick_and_long_delay_is_rejected() {
+        let mut rt = SnnRuntimePlus::new(8);
+        let a = rt.add_neuron(0.5);
+        let b = rt.add_neuron(0.5);
+        rt.add_edge(vec![a], vec![b], 1.0, 0);
+        rt.queue().schedule(SpikeEvent::spike(a, 0)).unwrap();
+        rt.step_once();
+        let spikes = rt.step_once();
+        assert_eq!(spikes.iter().map(|s| (s.neuron_id, s.time)).collect::<Vec<_>>(), vec![(b, 1)]);
+        rt.set_wheel_growth(false);
+        let long = rt.try_add_edge_with(vec![b], vec![a], 1.0, 9, EdgeOptions::default());
+        assert_eq!(long, Err(EdgeError::DelayBeyondHorizon { edge: 1, delay: 9 }));
+        assert_eq!(rt.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_scheduled_control_events_form_a_protocol() {
+        use snn_core::{EventKind, NeuronParam};
+        let mut rt = SnnRuntimePlus::new(16);
+        let a = rt.add_neuron(1.0);
+        let b = rt.add_neuron(1.0);
+        rt.add_edge(vec![a], vec![b], 0.6, 1);
+        // Raising b's threshold at t=2 makes the second drive of a insufficient
+        rt.queue().schedule(SpikeEvent::current(a, 0, to_fixed(1.0))).unwrap();
+        rt.queue().schedule(SpikeEvent::set_param(b, 2, NeuronParam::Threshold, to_fixed(2.0))).unwrap();
+        rt.queue().schedule(SpikeEvent::marker(2, 7)).unwrap();
+        rt.queue().schedule(SpikeEvent::spike(a, 3)).unwrap();
+        let mut log = Vec::new();
+        for _ in 0..6 {
+            log.extend(rt.step_once().into_iter().map(|e| (e.time, e.neuron_id, e.kind)));
+        }
+        assert_eq!(
+            log,
+            vec![
+                (0, a, EventKind::Current(to_fixed(1.0))),
+                (1, a, EventKind::Spike),
+                (2, b, EventKind::SetParam(NeuronParam::Threshold, to_fixed(2.0))),
+                (2, SpikeEvent::NO_NEURON, EventKind::Marker(7)),
+                (3, a, EventKind::Spike),
+            ]
+        );
+        assert_eq!(rt.neurons().membrane[b as usize], 2 * to_fixed(0.6));
+    }
+
+    #[cfg(feature = "plasticity")]
+    #[test]
+    fn test_scheduled_reward_commits_eligibility() {
+        use crate::plasticity::RewardModulatedStdp;
+        let mut rt = SnnRuntimePlus::new(16);
+        let pre = rt.add_neuron(1.0);
+        let post = rt.add_neuron(0.4);
+        rt.add_edge(vec![pre], vec![post], 0.5, 1);
+        rt.set_plasticity(RewardModulatedStdp::with_defaults());
+        rt.queue().schedule(SpikeEvent::spike(pre, 0)).unwrap();
+        rt.queue().schedule(SpikeEvent::reward(4, to_fixed(1.0))).unwrap();
+        rt.run_until(3);
+        assert_eq!(rt.edges().weight(0), to_fixed(0.5));
+        rt.run_until(4);
+        assert!(rt.edges().weight(0) > to_fixed(0.5));
+    }
+
+    #[test]
+    fn test_sub_tick_offsets_ride_through_delays() {
+        let mut rt = SnnRuntimePlus::new(16);
+        let a = rt.add_neuron(1.0);
+        let b = rt.add_neuron(1.0);
+        let c = rt.add_neuron(1.0);
+        rt.add_edge(vec![a], vec![c], 1.0, 2);
+        rt.add_edge(vec![b], vec![c], 1.0, 2);
+        rt.queue().set_order(snn_core::EventOrder::Time);
+        rt.queue().schedule(SpikeEvent::spike(a, 0).with_offset(0xC000)).unwrap();
+        rt.queue().schedule(SpikeEvent::spike(b, 0).with_offset(0x4000)).unwrap();
+        let first = rt.step_once();
+        assert_eq!(first.iter().map(|e| e.neuron_id).collect::<Vec<_>>(), vec![b, a]);
+        rt.step_once();
+        // Both inputs cross c's threshold; each keeps its input's offset
+        let arrived = rt.step_once();
+        assert_eq!(arrived.iter().map(|e| e.exact_time()).collect::<Vec<_>>(), vec![2.25, 2.75]);
+    }
+
+    #[test]
+    fn test_ms_edges_use_the_time_base() {
+        let mut rt = SnnRuntimePlus::new(64);
+        rt.set_time_base(TimeBase::from_tick_ms(0.1));
+        let a = rt.add_neuron(1.0);
+        let b = rt.add_neuron(1.0);
+        let opts = EdgeOptions { synapse: SynapseKind::ExpCurrent { tau: 2.0 }, ..Default::default() };
+        rt.add_edge_with_ms(vec![a], vec![b], 1.0, 1.5, opts);
+        rt.add_edge_ms(vec![b], vec![a], 1.0, 0.25);
+        assert_eq!((rt.edges().delay(0), rt.edges().delay(1)), (15, 3));
+        assert!(matches!(rt.synapses().channels()[0].kind(), SynapseKind::ExpCurrent { tau } if (tau - 20.0).abs() < 1e-4));
+        // Only the rounded delay is reported
+        assert_eq!(rt.take_time_warnings().len(), 1);
+
+        rt.queue().schedule(SpikeEvent::spike(a, 10)).unwrap();
+        rt.run_until(9);
+        let report = rt.step_with_report(StepBudgets::default());
+        assert!((report.time_ms - 1.0).abs() < 1e-9);
+        assert!((rt.now_ms() - 1.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_run_until_condition_stops_at_first_output_spike() {
+        let mut rt = SnnRuntimePlus::new(16);
+        let input = rt.add_neuron(1.0);
+        let hidden = rt.add_neuron(1.0);
+        let out = rt.add_neuron(1.0);
+        rt.add_edge(vec![input], vec![hidden], 1.0, 2);
+        rt.add_edge(vec![hidden], vec![out], 1.0, 3);
+        rt.queue().schedule(SpikeEvent::spike(input, 1)).unwrap();
+        let conditions = [StopCondition::MaxTicks(100), StopCondition::NeuronFired(out)];
+        let summary = rt.run_until_condition(&conditions);
+        assert_eq!(summary.index, 1);
+        assert_eq!(summary.condition, StopCondition::NeuronFired(out));
+        assert_eq!((summary.ticks, summary.spikes, summary.last_tick), (7, 3, Some(6)));
+
+        // Nothing left: quiescence wins over the (later) tick limit
+        let summary = rt.run_until_condition(&[StopCondition::Quiescent, StopCondition::MaxTicks(100)]);
+        assert_eq!((summary.index, summary.ticks), (0, 0));
+    }
+
+    #[test]
+    fn test_run_until_condition_activity_and_limits() {
+        let mut rt = SnnRuntimePlus::new(16);
+        let ids: Vec<NeuronId> = (0..4).map(|_| rt.add_neuron(1.0)).collect();
+        for t in 0..3 {
+            for &n in &ids[..=t] {
+                rt.queue().schedule(SpikeEvent::spike(n, t as u64)).unwrap();
+            }
+        }
+        let above = StopCondition::ActivityAbove { population: 0..4, threshold: 1 };
+        assert_eq!(rt.run_until_condition(&[above]).last_tick, Some(1));
+        let below = StopCondition::ActivityBelow { population: 1..4, threshold: 1 };
+        let summary = rt.run_until_condition(&[StopCondition::SpikeCount(10), below.clone()]);
+        assert_eq!((summary.condition, summary.spikes, summary.last_tick), (below, 3, Some(3)));
+        assert_eq!(rt.run_until_condition(&[StopCondition::MaxTicks(5)]).ticks, 5);
+    }
+
+    #[test]
+    fn test_fork_shares_topology_and_branches_independently() {
+        let mut rt = SnnRuntimePlus::new(16);
+        let a = rt.add_neuron(1.0);
+        let b = rt.add_neuron(1.0);
+        let c = rt.add_neuron(1.0);
+        rt.add_edge(vec![a], vec![b], 0.6, 1);
+        rt.add_edge(vec![c], vec![b], 0.6, 1);
+        rt.queue().schedule(SpikeEvent::spike(a, 0)).unwrap();
+        rt.run_until(1);
+
+        let mut branch = rt.fork().unwrap();
+        assert!(Arc::ptr_eq(rt.inner.shared_edges(), branch.inner.shared_edges()));
+        // Only the branch gets the second input, so only there does b reach threshold
+        branch.queue().schedule(SpikeEvent::spike(c, 2)).unwrap();
+        let fired = branch.run_until_condition(&[StopCondition::NeuronFired(b), StopCondition::MaxTicks(5)]);
+        assert_eq!(fired.condition, StopCondition::NeuronFired(b));
+        let quiet = rt.run_until_condition(&[StopCondition::NeuronFired(b), StopCondition::MaxTicks(5)]);
+        assert_eq!(quiet.condition, StopCondition::MaxTicks(5));
+        assert_eq!(rt.neurons().membrane[b as usize], to_fixed(0.6));
+    }
+
+    #[test]
+    fn test_reset_state_replays_like_a_fresh_network() {
+        let build = || {
+            let mut rt = SnnRuntimePlus::new(16);
+            let a = rt.add_neuron(0.5);
+            let b = rt.add_neuron(1.0);
+            let opts = EdgeOptions { stp: Some(StpParams::depressing(0.5, 20.0)), ..Default::default() };
+            rt.add_edge_with(vec![a], vec![b], 0.8, 1, opts);
+            rt.add_edge(vec![b], vec![a], 0.3, 2);
+            rt
+        };
+        let stimulate = |rt: &mut SnnRuntimePlus| {
+            for t in [0, 3, 4, 9] {
+                rt.queue().schedule(SpikeEvent::spike(0, t)).unwrap();
+            }
+            let mut out = Vec::new();
+            for _ in 0..15 {
+                out.extend(rt.step_once().iter().map(|e| (e.neuron_id, e.time)));
+            }
+            out
+        };
+        let mut rt = build();
+        let first = stimulate(&mut rt);
+        rt.reset_state();
+        assert_eq!(rt.inner.queue.current_time, 0);
+        assert_eq!(stimulate(&mut rt), first);
+        assert_eq!(stimulate(&mut build()), first);
+    }
+
+    #[cfg(feature = "plasticity")]
+    #[test]
+    fn test_learning_in_a_fork_leaves_the_parent_untouched() {
+        use crate::plasticity::{PairStdp, StdpPairing};
+        let mut rt = SnnRuntimePlus::new(16);
+        let pre = rt.add_neuron(1.0);
+        let post = rt.add_neuron(1.0);
+        rt.add_edge(vec![pre], vec![post], 0.5, 1);
+        rt.set_plasticity(PairStdp::new(StdpPairing::AllToAll, 0.1, 0.1, 20.0, 20.0, 0.0, 2.0));
+
+        let mut branch = rt.fork().unwrap();
+        branch.queue().schedule(SpikeEvent::spike(post, 0)).unwrap();
+        branch.queue().schedule(SpikeEvent::spike(pre, 5)).unwrap();
+        branch.run_until(6);
+        assert!(branch.edges().weight(0) < to_fixed(0.5));
+        assert_eq!(rt.edges().weight(0), to_fixed(0.5));
+        assert!(!Arc::ptr_eq(rt.inner.shared_edges(), branch.inner.shared_edges()));
+    }
+
+    #[cfg(feature = "plasticity")]
+    #[test]
+    fn test_stdp_learns_inhibitory_edges_by_magnitude() {
+        use crate::plasticity::QuantizedStdp;
+        let mut rt = SnnRuntimePlus::new(16);
+        let pre = rt.add_neuron(1.0);
+        let post = rt.add_neuron(1.0);
+        rt.add_edge(vec![pre], vec![post], -0.5, 1);
+        rt.set_plasticity(QuantizedStdp::with_defaults());
+
+        // A lone pre spike has nothing to pair with and must not zero the edge
+        rt.queue().schedule(SpikeEvent::spike(pre, 0)).unwrap();
+        rt.run_until(2);
+        assert_eq!(rt.edges().weight(0), to_fixed(-0.5));
+
+        // Pre before post potentiates: the edge becomes more inhibitory
+        rt.queue().schedule(SpikeEvent::spike(post, 3)).unwrap();
+        rt.run_until(4);
+        assert!(rt.edges().weight(0) < to_fixed(-0.5));
+    }
+
+    #[cfg(feature = "plasticity")]
+    #[test]
+    fn test_fork_names_the_rule_that_cannot_fork() {
+        use crate::plasticity::{PlasticityRule, QuantizedStdp};
+        use crate::plasticity_stack::RuleScope;
+        struct Opaque;
+        impl PlasticityRule for Opaque {
+            fn decay(&mut self) {}
+            fn on_pre_spike(&mut self, _: NeuronId, _: u64) {}
+            fn on_post_spike(&mut self, _: NeuronId, _: u64) {}
+            fn apply_pre(&mut self, _: u32, _: NeuronId, _: NeuronId, _: u64, _: &mut i32) {}
+            fn apply_post(&mut self, _: u32, _: NeuronId, _: NeuronId, _: u64, _: &mut i32) {}
+        }
+        let mut rt = SnnRuntimePlus::new(16);
+        rt.add_plasticity("stdp", QuantizedStdp::with_defaults(), RuleScope::All);
+        rt.add_plasticity("opaque", Opaque, RuleScope::All);
+        assert_eq!(rt.fork().err(), Some(ForkError { rule: "opaque".to_string() }));
+        rt.remove_plasticity("opaque");
+        assert!(rt.fork().is_ok());
+    }
+
+    #[cfg(not(feature = "plasticity"))]
+    #[test]
+    fn test_runtime_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<SnnRuntimePlus>();
+    }
+
+    #[cfg(feature = "id16")]
+    #[test]
+    fn test_id16_neuron_ids_are_16_bit() {
+        assert_eq!(core::mem::size_of::<NeuronId>(), 2);
+        assert_eq!(snn_core::neuron_id(65_534), 65_534);
+        assert!(std::panic::catch_unwind(|| snn_core::neuron_id(65_536)).is_err());
+        let mut rt = SnnRuntimePlus::new(8);
+        let a = rt.add_neuron(1.0);
+        let b = rt.add_neuron(1.0);
+        rt.add_edge(vec![a], vec![b], 1.0, 1);
+        rt.queue().schedule(SpikeEvent::spike(a, 0)).unwrap();
+        assert_eq!(rt.step_once()[0].neuron_id, a);
+        let fired: Vec<u16> = rt.step_once().iter().map(|e| e.neuron_id).collect();
+        assert_eq!(fired, vec![b]);
+    }
+
+    #[test]
+    fn test_wheel_grows_for_long_delays_and_keeps_pending_events() {
+        let mut rt = SnnRuntimePlus::auto_sized();
+        let a = rt.add_neuron(0.5);
+        let b = rt.add_neuron(0.5);
+        let c = rt.add_neuron(0.5);
+        rt.add_edge(vec![a], vec![b], 1.0, 3);
+        assert_eq!(rt.queue().wheel_size(), snn_core::AUTO_WHEEL_SIZE);
+        // Pending events spread over the old wheel, some in slots that move when it grows
+        for t in [2, 9, 15] {
+            rt.queue().schedule(SpikeEvent::spike(a, t)).unwrap();
+        }
+        rt.queue().schedule(SpikeEvent::marker(15, 7)).unwrap();
+        rt.run_until(1);
+
+        rt.add_edge(vec![b], vec![c], 1.0, 40);
+        assert_eq!((rt.max_delay(), rt.queue().wheel_size()), (40, 64));
+        assert!(rt.validate().is_ok());
+        assert_eq!(rt.queue().pending_count(), 4);
+
+        let summary = rt.run_until_condition(&[StopCondition::SpikeCount(9), StopCondition::MaxTicks(100)]);
+        assert_eq!(summary.condition, StopCondition::SpikeCount(9));
+        // a at 2, 9, 15 -> b three ticks later -> c forty ticks after that
+        assert_eq!(summary.last_tick, Some(58));
+    }
+
+    #[test]
+    fn test_disabled_wheel_growth_rejects_long_delay() {
+        let mut rt = SnnRuntimePlus::new(8);
+        let a = rt.add_neuron(1.0);
+        let b = rt.add_neuron(1.0);
+        rt.set_wheel_growth(false);
+        assert_eq!(rt.try_add_edge_with(vec![a], vec![b], 1.0, 8, EdgeOptions::default()), Ok(0));
+        assert_eq!(
+            rt.try_add_edge_with(vec![a], vec![b], 1.0, 9, EdgeOptions::default()),
+            Err(EdgeError::DelayBeyondHorizon { edge: 1, delay: 9 })
+        );
+        // The rejected delay is not recorded
+        assert_eq!((rt.edges().len(), rt.queue().wheel_size(), rt.max_delay()), (1, 8, 8));
+    }
+
+    #[test]
+    #[should_panic(expected = "try_add_typed_edge")]
+    fn test_add_edge_panics_on_delay_beyond_fixed_wheel() {
+        let mut rt = SnnRuntimePlus::new(8);
+        let a = rt.add_neuron(1.0);
+        rt.set_wheel_growth(false);
+        rt.add_edge(vec![a], vec![a], 1.0, 9);
+    }
+}
@@ -0,0 +1,141 @@
+//! Short-term synaptic plasticity (Tsodyks–Markram) for snn-core-plus.
+//!
+//! Each hyperedge configured with StpParams keeps a utilization `u` and available resources
+//! `x` (Q16.16). On a presynaptic spike at time t, after exact recovery over the interval
+//! since the previous spike:
+//! - u = u * exp(-Δt / tau_facil);            x = 1 + (x - 1) * exp(-Δt / tau_rec)
+//! - u += U * (1 - u)                          (facilitation)
+//! - delivered weight = weight * u * x         (HyperEdge.weight is the absolute efficacy)
+//! - x -= u * x                                (depression)
+//!
+//! This is the variant where `u` relaxes to 0 (as in NEST's tsodyks2_synapse), so a rested
+//! synapse releases `U` and tau_facil = 0 gives pure depression.
+//!
+//! The state is per hyperedge: all sources of an edge share one resource pool. Long-term
+//! rules keep changing HyperEdge.weight independently; STP only scales what is delivered.
+
+use snn_core::{fixed_mul, fixed_pow, to_fixed, Fixed, TimeBase, SCALE};
+
+/// Tsodyks–Markram parameters; time constants are in ticks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StpParams {
+    /// Baseline utilization U in (0, 1]
+    pub u: f32,
+    /// Recovery time constant of resources (depression)
+    pub tau_rec: f32,
+    /// Facilitation time constant (0 disables facilitation)
+    pub tau_facil: f32,
+}
+
+impl StpParams {
+    /// Depressing synapse (no facilitation)
+    pub fn depressing(u: f32, tau_rec: f32) -> Self {
+        Self { u, tau_rec, tau_facil: 0.0 }
+    }
+
+    /// Facilitating synapse
+    pub fn facilitating(u: f32, tau_rec: f32, tau_facil: f32) -> Self {
+        Self { u, tau_rec, tau_facil }
+    }
+
+    /// Read the time constants as milliseconds and convert them to ticks.
+    pub fn to_ticks(self, time_base: &TimeBase) -> Self {
+        Self { tau_rec: time_base.tau_ticks(self.tau_rec), tau_facil: time_base.tau_ticks(self.tau_facil), ..self }
+    }
+}
+
+#[inline]
+fn decay_factor(tau: f32) -> Fixed {
+    if tau <= 0.0 {
+        0
+    } else {
+        to_fixed((-1.0 / tau).exp())
+    }
+}
+
+/// Per-edge dynamic state (fixed-point).
+#[derive(Clone, Copy, Debug)]
+pub struct StpState {
+    u_base: Fixed,
+    alpha_rec: Fixed,
+    alpha_facil: Fixed,
+    /// Utilization after the last spike (0 when rested)
+    pub u: Fixed,
+    /// Available resources after the last spike
+    pub x: Fixed,
+    last_spike: Option<u64>,
+}
+
+impl StpState {
+    pub fn new(params: StpParams) -> Self {
+        let u_base = to_fixed(params.u);
+        Self {
+            u_base,
+            alpha_rec: decay_factor(params.tau_rec),
+            alpha_facil: decay_factor(params.tau_facil),
+            u: 0,
+            x: SCALE,
+            last_spike: None,
+        }
+    }
+
+    /// Register a presynaptic spike at `t` and return the release factor u * x (Q16.16).
+    pub fn on_spike(&mut self, t: u64) -> Fixed {
+        let (mut u, mut x) = match self.last_spike {
+            None => (0, SCALE),
+            Some(last) => {
+                // Exact relaxation over the inter-spike interval
+                let dt = t.saturating_sub(last);
+                let u = fixed_mul(self.u, fixed_pow(self.alpha_facil, dt));
+                let x = SCALE - fixed_mul(SCALE - self.x, fixed_pow(self.alpha_rec, dt));
+                (u, x)
+            }
+        };
+        u = u.saturating_add(fixed_mul(self.u_base, SCALE - u)).min(SCALE);
+        let release = fixed_mul(u, x);
+        x = x.saturating_sub(release).max(0);
+        self.u = u;
+        self.x = x;
+        self.last_spike = Some(t);
+        release
+    }
+
+    /// Restore the fully recovered state
+    pub fn reset(&mut self) {
+        self.u = 0;
+        self.x = SCALE;
+        self.last_spike = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snn_core::from_fixed;
+
+    #[test]
+    fn test_depression_recovers_exactly() {
+        let mut st = StpState::new(StpParams::depressing(0.5, 100.0));
+        assert!((from_fixed(st.on_spike(0)) - 0.5).abs() < 1e-3);
+        // x = 1 + (0.5 - 1) * e^{-0.1}
+        let x = 1.0 - 0.5 * (-0.1f32).exp();
+        assert!((from_fixed(st.on_spike(10)) - 0.5 * x).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_facilitation_grows_release() {
+        let mut st = StpState::new(StpParams::facilitating(0.1, 1.0, 500.0));
+        let first = st.on_spike(0);
+        let second = st.on_spike(20);
+        let third = st.on_spike(40);
+        assert!(second > first && third > second);
+    }
+
+    #[test]
+    fn test_long_pause_returns_to_rest() {
+        let mut st = StpState::new(StpParams::facilitating(0.2, 10.0, 10.0));
+        let rested = st.on_spike(0);
+        st.on_spike(1);
+        assert_eq!(st.on_spike(100_000), rested);
+    }
+}
@@ -0,0 +1,258 @@
+//! Structural plasticity for snn-core-plus (feature "plasticity").
+//!
+//! - Prunes excitatory and inhibitory edges whose weight magnitude stays below
+//!   `prune_threshold` for at least `prune_after` ticks; modulatory edges are never pruned.
+//! - Grows new single-source, single-target hyperedges between neurons that spiked within
+//!   the last `coactivity_window` ticks, up to `max_edges` in total.
+//! - Runs once every `period` ticks; pair selection uses a seeded xorshift RNG so runs are
+//!   reproducible.
+//!
+//! The engine only plans changes; SnnRuntimePlus applies them so that edge ids (== index)
+//! and the source/target adjacency stay consistent. Pruning compacts the edge list, so the
+//! ids of later edges shift down. Every change is recorded as a StructuralEvent.
+
+use snn_core::{neuron_id, to_fixed, EdgeArena, EdgeKind, Fixed, NeuronId, SpikeEvent};
+
+/// Configuration of the structural plasticity engine.
+#[derive(Clone, Copy, Debug)]
+pub struct StructuralConfig {
+    /// Ticks between structural updates
+    pub period: u64,
+    /// Edges with |weight| strictly below this value are pruning candidates
+    pub prune_threshold: f32,
+    /// Ticks an edge must stay below the threshold before it is pruned
+    pub prune_after: u64,
+    /// Neurons that spiked within this many ticks count as co-active
+    pub coactivity_window: u64,
+    /// Maximum number of edges grown per update
+    pub grow_per_update: usize,
+    /// Initial weight and delay of grown edges
+    pub grow_weight: f32,
+    pub grow_delay: u64,
+    /// Cap on the total number of edges in the network
+    pub max_edges: usize,
+    /// RNG seed for pair selection
+    pub seed: u64,
+}
+
+impl Default for StructuralConfig {
+    fn default() -> Self {
+        Self {
+            period: 100,
+            prune_threshold: 0.01,
+            prune_after: 1000,
+            coactivity_window: 5,
+            grow_per_update: 1,
+            grow_weight: 0.1,
+            grow_delay: 1,
+            max_edges: 10_000,
+            seed: 0x5eed,
+        }
+    }
+}
+
+/// A structural change applied by the runtime.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StructuralEvent {
+    /// Edge removed; `edge_id` is its id before compaction
+    Pruned { time: u64, edge_id: u32, sources: Vec<NeuronId>, targets: Vec<NeuronId> },
+    /// Edge added with the given id
+    Grown { time: u64, edge_id: u32, source: NeuronId, target: NeuronId },
+}
+
+/// Changes to apply for one structural update.
+pub struct StructuralPlan {
+    /// Edge ids to remove (ascending)
+    pub prune: Vec<u32>,
+    /// (source, target) pairs to connect
+    pub grow: Vec<(NeuronId, NeuronId)>,
+}
+
+/// Minimal xorshift64* generator (zero-dependency, reproducible across targets).
+#[derive(Clone, Debug)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    pub fn new(seed: u64) -> Self {
+        // Zero is a fixed point of xorshift
+        Self { state: if seed == 0 { 0x9e37_79b9_7f4a_7c15 } else { seed } }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Uniform index in 0..n (n > 0)
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// Structural plasticity engine (see module docs).
+#[derive(Clone)]
+pub struct StructuralPlasticity {
+    config: StructuralConfig,
+    prune_threshold: Fixed,
+    rng: XorShift64,
+    // First update time at which each edge was seen below threshold (indexed by edge id)
+    below_since: Vec<Option<u64>>,
+    // Last spike time per neuron
+    last_spike: Vec<Option<u64>>,
+}
+
+impl StructuralPlasticity {
+    pub fn new(config: StructuralConfig) -> Self {
+        Self {
+            config,
+            prune_threshold: to_fixed(config.prune_threshold),
+            rng: XorShift64::new(config.seed),
+            below_since: Vec::new(),
+            last_spike: Vec::new(),
+        }
+    }
+
+    /// Forget spike times and pruning candidates and restart the generator from the seed.
+    pub fn reset(&mut self) {
+        self.rng = XorShift64::new(self.config.seed);
+        self.below_since.clear();
+        self.last_spike.clear();
+    }
+
+    pub fn config(&self) -> &StructuralConfig {
+        &self.config
+    }
+
+    /// Record the spikes of one tick
+    pub fn observe(&mut self, spikes: &[SpikeEvent]) {
+        for sp in spikes {
+            let idx = sp.neuron_id as usize;
+            if self.last_spike.len() <= idx {
+                self.last_spike.resize(idx + 1, None);
+            }
+            self.last_spike[idx] = Some(sp.time);
+        }
+    }
+
+    /// True when an update is due after the tick that ended at `now`
+    pub fn due(&self, now: u64) -> bool {
+        now.is_multiple_of(self.config.period.max(1))
+    }
+
+    /// Plan prunes and growth for time `now` on the (compiled) edges.
+    pub fn plan(&mut self, now: u64, edges: &EdgeArena) -> StructuralPlan {
+        // Pruning: track how long each edge has been weak
+        self.below_since.resize(edges.len(), None);
+        let mut prune = Vec::new();
+        for (i, edge) in edges.iter().enumerate() {
+            let weak = !matches!(edge.kind, EdgeKind::Modulatory(_)) && edge.weight.saturating_abs() < self.prune_threshold;
+            if weak {
+                let since = *self.below_since[i].get_or_insert(now);
+                if now.saturating_sub(since) >= self.config.prune_after {
+                    prune.push(i as u32);
+                }
+            } else {
+                self.below_since[i] = None;
+            }
+        }
+
+        // Growth: random pairs of co-active neurons without an existing connection
+        let mut grow = Vec::new();
+        let room = self.config.max_edges.saturating_sub(edges.len() - prune.len());
+        let budget = self.config.grow_per_update.min(room);
+        if budget > 0 {
+            let window = self.config.coactivity_window;
+            let active: Vec<NeuronId> = self
+                .last_spike
+                .iter()
+                .enumerate()
+                .filter(|(_, t)| t.is_some_and(|t| now.saturating_sub(t) <= window))
+                .map(|(i, _)| neuron_id(i))
+                .collect();
+            if active.len() >= 2 {
+                // Bounded number of draws keeps the update cost predictable
+                for _ in 0..budget.saturating_mul(4) {
+                    if grow.len() >= budget {
+                        break;
+                    }
+                    let pre = active[self.rng.below(active.len())];
+                    let post = active[self.rng.below(active.len())];
+                    if pre == post || grow.contains(&(pre, post)) {
+                        continue;
+                    }
+                    let connected = edges.outgoing(pre).iter().any(|&e| edges.targets(e).contains(&post));
+                    if !connected {
+                        grow.push((pre, post));
+                    }
+                }
+            }
+        }
+
+        StructuralPlan { prune, grow }
+    }
+
+    /// Follow `SnnRuntimePlus::remove_edges` (which also applies planned prunes): forget the
+    /// pruning clocks of the `removed` ids (ascending) and shift later ones down.
+    pub fn on_edges_removed(&mut self, removed: &[u32]) {
+        let mut id = 0u32;
+        self.below_since.retain(|_| {
+            id += 1;
+            removed.binary_search(&(id - 1)).is_err()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snn_core::{HyperEdge, Modulation};
+
+    fn edge(id: u32, s: NeuronId, t: NeuronId, w: f32) -> HyperEdge {
+        let kind = EdgeKind::from_weight(to_fixed(w));
+        HyperEdge { id, sources: vec![s], targets: vec![t], weight: to_fixed(w), delay: 1, kind }
+    }
+
+    #[test]
+    fn test_prune_after_sustained_weakness() {
+        let cfg = StructuralConfig { period: 10, prune_threshold: 0.1, prune_after: 20, ..Default::default() };
+        let mut sp = StructuralPlasticity::new(cfg);
+        let edges = EdgeArena::from_edges(&[edge(0, 0, 1, 0.05), edge(1, 1, 2, 0.5)]);
+        assert!(sp.plan(10, &edges).prune.is_empty());
+        assert!(sp.plan(20, &edges).prune.is_empty());
+        assert_eq!(sp.plan(30, &edges).prune, vec![0]);
+    }
+
+    #[test]
+    fn test_strong_inhibitory_and_modulatory_edges_survive() {
+        let cfg = StructuralConfig { period: 10, prune_threshold: 0.1, prune_after: 0, ..Default::default() };
+        let mut sp = StructuralPlasticity::new(cfg);
+        let mut modulatory = edge(2, 2, 0, 0.0);
+        modulatory.kind = EdgeKind::Modulatory(Modulation::Gain);
+        let edges = EdgeArena::from_edges(&[edge(0, 0, 1, -0.5), edge(1, 1, 2, -0.05), modulatory]);
+        // Only the weak inhibitory edge goes
+        assert_eq!(sp.plan(10, &edges).prune, vec![1]);
+    }
+
+    #[test]
+    fn test_grow_between_coactive_is_seeded_and_capped() {
+        let cfg = StructuralConfig { grow_per_update: 3, max_edges: 2, seed: 7, ..Default::default() };
+        let edges = EdgeArena::from_edges(&[edge(0, 0, 1, 0.5)]);
+        let spikes: Vec<SpikeEvent> = (0..3).map(|n| SpikeEvent::spike(n, 98)).collect();
+
+        let mut a = StructuralPlasticity::new(cfg);
+        let mut b = StructuralPlasticity::new(cfg);
+        a.observe(&spikes);
+        b.observe(&spikes);
+        let pa = a.plan(100, &edges);
+        let pb = b.plan(100, &edges);
+        assert_eq!(pa.grow, pb.grow);
+        assert_eq!(pa.grow.len(), 1);
+        assert!(!pa.grow.contains(&(0, 1)));
+    }
+}
@@ -0,0 +1,338 @@
+//! Synapse kernels for snn-core-plus: current- and conductance-based synaptic input.
+//!
+//! - Instant: the delivered weight jumps the membrane directly (snn-core behaviour).
+//! - ExpCurrent: I jumps by w and decays with tau; the membrane integrates I every tick.
+//! - AlphaCurrent: alpha-shaped current peaking at w after tau ticks (exact two-state propagator).
+//! - Conductance: g jumps by |w| and decays with tau; the membrane integrates g * (E_rev - V),
+//!   so E_rev alone decides whether the synapse excites or inhibits.
+//!
+//! Every distinct kernel is a channel holding per-neuron state, so neurons sum inputs of the
+//! same kind. Only neurons with non-zero state are visited, which keeps the per-tick cost
+//! proportional to synaptic activity. Arrivals respect edge delays: input scheduled for tick
+//! T enters the state during tick T. All arithmetic is Q16.16 like snn-core.
+
+use snn_core::{fixed_mul, to_fixed, Fixed, NeuronId, TimeBase};
+
+use crate::neuron_store::NeuronStore;
+
+/// Kernel of a hyperedge's synapses; time constants are in ticks, potentials in membrane units.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SynapseKind {
+    #[default]
+    Instant,
+    ExpCurrent { tau: f32 },
+    AlphaCurrent { tau: f32 },
+    Conductance { tau: f32, e_rev: f32 },
+}
+
+impl SynapseKind {
+    /// Read `tau` as milliseconds and convert it to ticks.
+    pub fn to_ticks(self, time_base: &TimeBase) -> Self {
+        match self {
+            SynapseKind::Instant => self,
+            SynapseKind::ExpCurrent { tau } => SynapseKind::ExpCurrent { tau: time_base.tau_ticks(tau) },
+            SynapseKind::AlphaCurrent { tau } => SynapseKind::AlphaCurrent { tau: time_base.tau_ticks(tau) },
+            SynapseKind::Conductance { tau, e_rev } => SynapseKind::Conductance { tau: time_base.tau_ticks(tau), e_rev },
+        }
+    }
+}
+
+#[inline]
+fn decay_factor(tau: f32) -> Fixed {
+    if tau <= 0.0 {
+        0
+    } else {
+        to_fixed((-1.0 / tau).exp())
+    }
+}
+
+/// Per-neuron state of one kernel.
+#[derive(Clone)]
+pub struct SynapseChannel {
+    kind: SynapseKind,
+    alpha: Fixed,
+    // Alpha kernel: rise-state jump per unit weight (e / tau), so the current peaks at w
+    rise_gain: Fixed,
+    e_rev: Fixed,
+    // Current (I) or conductance (g) per neuron
+    state: Vec<Fixed>,
+    // Rise state of the alpha kernel per neuron
+    rise: Vec<Fixed>,
+    // Neurons with non-zero state, and membership flags
+    active: Vec<NeuronId>,
+    in_active: Vec<bool>,
+    // Arrivals not yet due: (time, target, weight)
+    pending: Vec<(u64, NeuronId, Fixed)>,
+}
+
+impl SynapseChannel {
+    pub fn new(kind: SynapseKind) -> Self {
+        let (tau, e_rev) = match kind {
+            SynapseKind::Instant => (0.0, 0.0),
+            SynapseKind::ExpCurrent { tau } | SynapseKind::AlphaCurrent { tau } => (tau, 0.0),
+            SynapseKind::Conductance { tau, e_rev } => (tau, e_rev),
+        };
+        Self {
+            kind,
+            alpha: decay_factor(tau),
+            rise_gain: if tau > 0.0 { to_fixed(core::f32::consts::E / tau) } else { 0 },
+            e_rev: to_fixed(e_rev),
+            state: Vec::new(),
+            rise: Vec::new(),
+            active: Vec::new(),
+            in_active: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn kind(&self) -> SynapseKind {
+        self.kind
+    }
+
+    /// Queue input of `weight` for `target` arriving at tick `time`. Conductance kernels take
+    /// its magnitude (a conductance is never negative; inhibitory edges carry negative weights).
+    pub fn receive(&mut self, time: u64, target: NeuronId, weight: Fixed) {
+        let weight = match self.kind {
+            SynapseKind::Conductance { .. } => weight.saturating_abs(),
+            _ => weight,
+        };
+        self.pending.push((time, target, weight));
+    }
+
+    /// Current (or conductance) state of a neuron.
+    pub fn state(&self, neuron: NeuronId) -> Fixed {
+        *self.state.get(neuron as usize).unwrap_or(&0)
+    }
+
+    fn activate(&mut self, n: usize) {
+        if self.state.len() <= n {
+            self.state.resize(n + 1, 0);
+            self.rise.resize(n + 1, 0);
+            self.in_active.resize(n + 1, false);
+        }
+        if !self.in_active[n] {
+            self.in_active[n] = true;
+            self.active.push(n as NeuronId);
+        }
+    }
+
+    /// Advance one tick at time `t`: apply due arrivals, integrate into neurons, decay.
+    /// Neurons that cross threshold are appended to `fired` with the interpolated crossing
+    /// point inside the tick (1/65536 tick units).
+    pub fn step(&mut self, t: u64, neurons: &mut NeuronStore, fired: &mut Vec<(NeuronId, u16)>) {
+        // Due arrivals enter the state (order-independent sums)
+        let mut i = 0;
+        while i < self.pending.len() {
+            let (time, target, w) = self.pending[i];
+            if time <= t {
+                self.pending.swap_remove(i);
+                let n = target as usize;
+                self.activate(n);
+                match self.kind {
+                    SynapseKind::AlphaCurrent { .. } => {
+                        self.rise[n] = self.rise[n].saturating_add(fixed_mul(w, self.rise_gain));
+                    }
+                    _ => self.state[n] = self.state[n].saturating_add(w),
+                }
+            } else {
+                i += 1;
+            }
+        }
+
+        // Integrate and decay active neurons in id order (deterministic)
+        self.active.sort_unstable();
+        let mut k = 0;
+        while k < self.active.len() {
+            let n = self.active[k] as usize;
+            let s = self.state[n];
+            if let Some(&v) = neurons.view().membrane.get(n) {
+                let input = match self.kind {
+                    SynapseKind::Conductance { .. } => fixed_mul(s, self.e_rev.saturating_sub(v)),
+                    _ => s,
+                };
+                if input != 0 {
+                    if let Some(offset) = neurons.inject_spread(n as NeuronId, input, t) {
+                        fired.push((n as NeuronId, offset));
+                    }
+                }
+            }
+            // Exact propagators: I' = a*I (+ a*y for alpha), y' = a*y
+            if let SynapseKind::AlphaCurrent { .. } = self.kind {
+                let y = self.rise[n];
+                self.state[n] = fixed_mul(s.saturating_add(y), self.alpha);
+                self.rise[n] = fixed_mul(y, self.alpha);
+            } else {
+                self.state[n] = fixed_mul(s, self.alpha);
+            }
+            if self.state[n] == 0 && self.rise[n] == 0 {
+                self.in_active[n] = false;
+                self.active.swap_remove(k);
+            } else {
+                k += 1;
+            }
+        }
+    }
+
+    /// None while any neuron has synaptic state (the channel changes every tick);
+    /// otherwise Some(earliest pending arrival time, or None when there is none).
+    pub fn next_activity(&self) -> Option<Option<u64>> {
+        if !self.active.is_empty() {
+            return None;
+        }
+        Some(self.pending.iter().map(|&(t, _, _)| t).min())
+    }
+
+    /// Clear all synaptic state and pending arrivals.
+    pub fn reset(&mut self) {
+        self.state.iter_mut().for_each(|s| *s = 0);
+        self.rise.iter_mut().for_each(|s| *s = 0);
+        self.in_active.iter_mut().for_each(|a| *a = false);
+        self.active.clear();
+        self.pending.clear();
+    }
+}
+
+/// All kernel channels of a network; identical kinds share one channel.
+#[derive(Clone, Default)]
+pub struct SynapseBank {
+    channels: Vec<SynapseChannel>,
+}
+
+impl SynapseBank {
+    /// Channel index for a kernel (None for Instant), creating it on first use.
+    pub fn channel_for(&mut self, kind: SynapseKind) -> Option<u16> {
+        if kind == SynapseKind::Instant {
+            return None;
+        }
+        let idx = match self.channels.iter().position(|c| c.kind == kind) {
+            Some(i) => i,
+            None => {
+                self.channels.push(SynapseChannel::new(kind));
+                self.channels.len() - 1
+            }
+        };
+        Some(idx as u16)
+    }
+
+    pub fn channels(&self) -> &[SynapseChannel] {
+        &self.channels
+    }
+
+    pub fn receive(&mut self, channel: u16, time: u64, target: NeuronId, weight: Fixed) {
+        if let Some(c) = self.channels.get_mut(channel as usize) {
+            c.receive(time, target, weight);
+        }
+    }
+
+    /// Advance every channel one tick at time `t`.
+    pub fn step(&mut self, t: u64, neurons: &mut NeuronStore, fired: &mut Vec<(NeuronId, u16)>) {
+        for c in &mut self.channels {
+            c.step(t, neurons, fired);
+        }
+    }
+
+    /// First tick at which any channel must be stepped: None if some channel is active
+    /// (every tick matters), Some(None) if all channels are idle with nothing pending.
+    pub fn next_activity(&self) -> Option<Option<u64>> {
+        let mut next: Option<u64> = None;
+        for c in &self.channels {
+            if let Some(t) = c.next_activity()? {
+                next = Some(next.map_or(t, |n| n.min(t)));
+            }
+        }
+        Some(next)
+    }
+
+    pub fn reset(&mut self) {
+        self.channels.iter_mut().for_each(SynapseChannel::reset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snn_core::from_fixed;
+
+    fn quiet_neuron() -> NeuronStore {
+        let mut neurons = NeuronStore::default();
+        neurons.push(1000.0);
+        neurons
+    }
+
+    #[test]
+    fn test_exp_current_decays() {
+        let mut ch = SynapseChannel::new(SynapseKind::ExpCurrent { tau: 10.0 });
+        let mut neurons = quiet_neuron();
+        let mut fired = Vec::new();
+        ch.receive(0, 0, to_fixed(1.0));
+        ch.step(0, &mut neurons, &mut fired);
+        assert!((from_fixed(neurons.view().membrane[0]) - 1.0).abs() < 1e-3);
+        for t in 1..5 {
+            ch.step(t, &mut neurons, &mut fired);
+        }
+        // Membrane = sum_{k=0..4} e^{-k/10}
+        let expect: f32 = (0..5).map(|k| (-(k as f32) / 10.0).exp()).sum();
+        assert!((from_fixed(neurons.view().membrane[0]) - expect).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_alpha_current_peaks_at_tau() {
+        let mut ch = SynapseChannel::new(SynapseKind::AlphaCurrent { tau: 4.0 });
+        let mut neurons = quiet_neuron();
+        let mut fired = Vec::new();
+        ch.receive(0, 0, to_fixed(1.0));
+        let mut currents = Vec::new();
+        for t in 0..12 {
+            ch.step(t, &mut neurons, &mut fired);
+            currents.push(from_fixed(ch.state(0)));
+        }
+        // state after step t is the current for tick t + 1: w * (n/tau) * e^{1 - n/tau}
+        let peak = currents.iter().cloned().fold(f32::MIN, f32::max);
+        assert!((peak - 1.0).abs() < 1e-2);
+        assert!((currents[3] - peak).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_conductance_drives_toward_reversal() {
+        let mut ch = SynapseChannel::new(SynapseKind::Conductance { tau: 5.0, e_rev: -0.5 });
+        let mut neurons = quiet_neuron();
+        neurons.membrane_mut()[0] = to_fixed(0.5);
+        let mut fired = Vec::new();
+        ch.receive(0, 0, to_fixed(0.5));
+        for t in 0..50 {
+            ch.step(t, &mut neurons, &mut fired);
+        }
+        let v = from_fixed(neurons.view().membrane[0]);
+        assert!((-0.5..0.5).contains(&v));
+    }
+
+    #[test]
+    fn test_arrival_respects_delay_and_sharing() {
+        let mut bank = SynapseBank::default();
+        let a = bank.channel_for(SynapseKind::ExpCurrent { tau: 2.0 });
+        let b = bank.channel_for(SynapseKind::ExpCurrent { tau: 2.0 });
+        assert_eq!(a, b);
+        assert_eq!(bank.channel_for(SynapseKind::Instant), None);
+
+        let mut neurons = quiet_neuron();
+        let mut fired = Vec::new();
+        bank.receive(a.unwrap(), 3, 0, to_fixed(1.0));
+        bank.step(2, &mut neurons, &mut fired);
+        assert_eq!(neurons.view().membrane[0], 0);
+        bank.step(3, &mut neurons, &mut fired);
+        assert!(neurons.view().membrane[0] > 0);
+    }
+
+    #[test]
+    fn test_crossing_offset_is_interpolated() {
+        let mut ch = SynapseChannel::new(SynapseKind::ExpCurrent { tau: 10.0 });
+        let mut neurons = NeuronStore::default();
+        neurons.push(1.0);
+        neurons.membrane_mut()[0] = to_fixed(0.5);
+        let mut fired = Vec::new();
+        ch.receive(0, 0, to_fixed(2.0));
+        ch.step(0, &mut neurons, &mut fired);
+        // 0.5 of the 2.0 delivered this tick reaches threshold: a quarter into the tick
+        assert_eq!(fired, vec![(0, 0x4000)]);
+    }
+}
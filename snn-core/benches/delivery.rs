@@ -0,0 +1,171 @@
+//! Spike delivery throughput: per-neuron adjacency lists vs the edge arena.
+//! Run with `cargo bench -p snn-core` (optionally `-- <neurons> <edges>`).
+//!
+//! Both paths visit only the spiking neuron's outgoing edges, so this measures memory
+//! layout, not algorithmic savings. The baseline is the layout the runtimes used before
+//! `EdgeArena`: a `Vec<HyperEdge>` (one heap allocation per source/target list) indexed by
+//! a `Vec<Vec<u32>>` source -> edges adjacency. The arena path is `SnnRuntime::step_once`,
+//! which walks `EdgeArena::fan_out`: a spiking neuron's edges and their targets are
+//! contiguous, so a spike touches a few cache lines instead of one per edge.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use snn_core::{neuron_id, HyperEdge, Neuron, NeuronId, SnnRuntime, SpikeEvent, TimeWheel};
+
+const FAN_OUT: usize = 4;
+const SEED_SPIKES: u64 = 20_000;
+const TICKS: u64 = 100;
+const WHEEL: u64 = 256;
+
+// Small LCG so the network is reproducible without dependencies
+struct Lcg(u64);
+
+impl Lcg {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 33) % n as u64) as usize
+    }
+
+    fn neuron(&mut self, n: usize) -> NeuronId {
+        neuron_id(self.below(n))
+    }
+}
+
+fn build(neurons: usize, edges: usize) -> SnnRuntime {
+    let mut rng = Lcg(0x5eed);
+    let mut rt = SnnRuntime::new(WHEEL);
+    for _ in 0..neurons {
+        // High threshold: measure delivery, not cascades
+        rt.add_neuron(1.0e6);
+    }
+    for _ in 0..edges {
+        let src = rng.neuron(neurons);
+        let tgts = (0..FAN_OUT).map(|_| rng.neuron(neurons)).collect();
+        rt.add_edge(vec![src], tgts, 0.01, 1 + rng.below(8) as u64);
+    }
+    rt.compile();
+    rt
+}
+
+fn seed(queue: &mut TimeWheel, neurons: usize) {
+    let mut rng = Lcg(0xfeed);
+    for i in 0..SEED_SPIKES {
+        queue.schedule(SpikeEvent::spike(rng.neuron(neurons), i % TICKS)).unwrap();
+    }
+}
+
+/// Baseline: nested per-neuron adjacency over owned edges, immediate delivery.
+struct Nested {
+    neurons: Vec<Neuron>,
+    edges: Vec<HyperEdge>,
+    source_to_edges: Vec<Vec<u32>>,
+    queue: TimeWheel,
+}
+
+impl Nested {
+    fn from_runtime(rt: &SnnRuntime) -> Self {
+        let edges = rt.edges().to_edges();
+        let mut source_to_edges = vec![Vec::new(); rt.neurons.len()];
+        for edge in &edges {
+            for &s in &edge.sources {
+                source_to_edges[s as usize].push(edge.id);
+            }
+        }
+        Self { neurons: rt.neurons.clone(), edges, source_to_edges, queue: TimeWheel::new(WHEEL) }
+    }
+
+    fn step_once(&mut self) -> Vec<SpikeEvent> {
+        let events = self.queue.next();
+        for ev in &events {
+            for &id in &self.source_to_edges[ev.neuron_id as usize] {
+                let edge = &self.edges[id as usize];
+                let time = ev.time + edge.delay;
+                for &tgt in &edge.targets {
+                    if self.neurons[tgt as usize].inject(edge.weight, time) {
+                        let _ = self.queue.schedule_clamped(SpikeEvent::spike(tgt, time));
+                    }
+                }
+            }
+        }
+        events
+    }
+}
+
+trait Deliver {
+    fn restart(&mut self, neurons: usize);
+    fn now(&self) -> u64;
+    fn step(&mut self) -> Vec<SpikeEvent>;
+}
+
+impl Deliver for SnnRuntime {
+    fn restart(&mut self, neurons: usize) {
+        self.reset_state();
+        seed(&mut self.queue, neurons);
+    }
+
+    fn now(&self) -> u64 {
+        self.queue.current_time
+    }
+
+    fn step(&mut self) -> Vec<SpikeEvent> {
+        self.step_once()
+    }
+}
+
+impl Deliver for Nested {
+    fn restart(&mut self, neurons: usize) {
+        self.neurons.iter_mut().for_each(|n| n.membrane = 0);
+        self.queue = TimeWheel::new(WHEEL);
+        seed(&mut self.queue, neurons);
+    }
+
+    fn now(&self) -> u64 {
+        self.queue.current_time
+    }
+
+    fn step(&mut self) -> Vec<SpikeEvent> {
+        self.step_once()
+    }
+}
+
+/// Fastest of `repeats` runs over `TICKS` ticks, in seconds.
+fn best_of(net: &mut impl Deliver, neurons: usize, repeats: usize) -> f64 {
+    let mut best = f64::INFINITY;
+    for _ in 0..repeats {
+        net.restart(neurons);
+        let start = Instant::now();
+        while net.now() < TICKS {
+            black_box(net.step());
+        }
+        best = best.min(start.elapsed().as_secs_f64());
+    }
+    best
+}
+
+fn main() {
+    let args: Vec<usize> = std::env::args().skip(1).filter_map(|a| a.parse().ok()).collect();
+    let neurons = args.first().copied().unwrap_or(10_000);
+    let edges = args.get(1).copied().unwrap_or(20_000);
+
+    let mut arena = build(neurons, edges);
+    let mut nested = Nested::from_runtime(&arena);
+
+    // Each seeded spike reaches every target of every outgoing edge
+    let mut seeded = TimeWheel::new(WHEEL);
+    seed(&mut seeded, neurons);
+    let deliveries: usize = seeded
+        .drain_range(0..TICKS)
+        .iter()
+        .flat_map(|ev| arena.edges().outgoing(ev.neuron_id))
+        .map(|&id| arena.edges().targets(id).len())
+        .sum();
+
+    let nested_s = best_of(&mut nested, neurons, 20);
+    let arena_s = best_of(&mut arena, neurons, 20);
+
+    println!("delivery: {neurons} neurons, {edges} edges, {SEED_SPIKES} spikes, {deliveries} deliveries");
+    println!("  Vec<Vec<u32>> adjacency {:>10.3} ms  {:>14.0} deliveries/s", nested_s * 1e3, deliveries as f64 / nested_s);
+    println!("  edge arena (CSR)        {:>10.3} ms  {:>14.0} deliveries/s", arena_s * 1e3, deliveries as f64 / arena_s);
+    println!("  speedup                 {:>10.2}x", nested_s / arena_s);
+}
@@ -0,0 +1,424 @@
+//! Flat (CSR) hyperedge storage
+//!
+//! Sources and targets of all edges live in two flat arrays addressed by per-edge offsets,
+//! and the source -> edges and target -> edges indexes are flat arrays addressed by
+//! per-neuron offsets. Edges are appended incrementally with `push`; `compile` then rebuilds
+//! the indexes and a per-source copy of the targets (`fan_out`), after which delivery
+//! touches only contiguous memory. `EdgeView` and `EdgeViewMut` give `HyperEdge`-like access.
+
+use crate::fixed::Fixed;
+use crate::hypergraph::{EdgeKind, HyperEdge};
+use crate::neuron::NeuronId;
+
+/// Borrowed view of one edge in an `EdgeArena`.
+#[derive(Clone, Copy, Debug)]
+pub struct EdgeView<'a> {
+    pub id: u32,
+    pub sources: &'a [NeuronId],
+    pub targets: &'a [NeuronId],
+    pub weight: Fixed,
+    pub delay: u64,
+    pub kind: EdgeKind,
+}
+
+impl EdgeView<'_> {
+    /// Owned copy in the `HyperEdge` layout.
+    pub fn to_edge(&self) -> HyperEdge {
+        HyperEdge {
+            id: self.id,
+            sources: self.sources.to_vec(),
+            targets: self.targets.to_vec(),
+            weight: self.weight,
+            delay: self.delay,
+            kind: self.kind,
+        }
+    }
+
+    /// True when the weight's sign agrees with the kind (see `HyperEdge::sign_ok`).
+    pub fn sign_ok(&self) -> bool {
+        self.kind.admits(self.weight)
+    }
+}
+
+/// View of one edge whose weight can be changed in place (topology stays frozen).
+#[derive(Debug)]
+pub struct EdgeViewMut<'a> {
+    pub id: u32,
+    pub sources: &'a [NeuronId],
+    pub targets: &'a [NeuronId],
+    pub weight: &'a mut Fixed,
+    pub delay: u64,
+    pub kind: EdgeKind,
+}
+
+impl EdgeViewMut<'_> {
+    /// Clamp the weight to the sign required by the kind (see `HyperEdge::clamp_to_kind`).
+    pub fn clamp_to_kind(&mut self) {
+        *self.weight = self.kind.clamp(*self.weight);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EdgeArena {
+    // Per edge (index == id)
+    weights: Vec<Fixed>,
+    delays: Vec<u64>,
+    kinds: Vec<EdgeKind>,
+    // Edge e owns sources[source_offsets[e]..source_offsets[e + 1]] (same for targets)
+    source_offsets: Vec<u32>,
+    sources: Vec<NeuronId>,
+    target_offsets: Vec<u32>,
+    targets: Vec<NeuronId>,
+    // Neuron n's outgoing edge ids (ascending) are out_edges[out_offsets[n]..out_offsets[n + 1]],
+    // its incoming ones in_edges[in_offsets[n]..in_offsets[n + 1]]; rebuilt by `compile`
+    out_offsets: Vec<u32>,
+    out_edges: Vec<u32>,
+    in_offsets: Vec<u32>,
+    in_edges: Vec<u32>,
+    // Delivery copy of the targets in out_edges order: slot k (an index into out_edges) owns
+    // out_targets[out_target_offsets[k]..out_target_offsets[k + 1]], so a spiking neuron's
+    // fan-out is one contiguous run; rebuilt by `compile`
+    out_target_offsets: Vec<u32>,
+    out_targets: Vec<NeuronId>,
+    compiled: bool,
+}
+
+/// One outgoing edge of a neuron as yielded by `EdgeArena::fan_out`.
+#[derive(Clone, Copy, Debug)]
+pub struct OutEdge<'a> {
+    pub id: u32,
+    pub targets: &'a [NeuronId],
+    pub weight: Fixed,
+    pub delay: u64,
+    pub kind: EdgeKind,
+}
+
+impl Default for EdgeArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Counting sort of edges by member neuron: (offsets, edge ids) with each neuron's edges in
+/// id order; an edge listing a neuron twice appears once.
+fn index_by_member(offsets: &[u32], members: &[NeuronId]) -> (Vec<u32>, Vec<u32>) {
+    let n = members.iter().map(|&m| m as usize + 1).max().unwrap_or(0);
+    let each = |f: &mut dyn FnMut(u32, NeuronId)| {
+        for (e, span) in offsets.windows(2).enumerate() {
+            let list = &members[span[0] as usize..span[1] as usize];
+            for (i, &m) in list.iter().enumerate() {
+                if !list[..i].contains(&m) {
+                    f(e as u32, m);
+                }
+            }
+        }
+    };
+    let mut counts = vec![0u32; n + 1];
+    each(&mut |_, m| counts[m as usize + 1] += 1);
+    for i in 1..counts.len() {
+        counts[i] += counts[i - 1];
+    }
+    let mut next = counts.clone();
+    let mut edges = vec![0u32; counts[n] as usize];
+    each(&mut |e, m| {
+        edges[next[m as usize] as usize] = e;
+        next[m as usize] += 1;
+    });
+    (counts, edges)
+}
+
+#[inline]
+fn neighbours<'a>(offsets: &[u32], edges: &'a [u32], neuron: NeuronId) -> &'a [u32] {
+    let n = neuron as usize;
+    match (offsets.get(n), offsets.get(n + 1)) {
+        (Some(&a), Some(&b)) => &edges[a as usize..b as usize],
+        _ => &[],
+    }
+}
+
+impl EdgeArena {
+    pub fn new() -> Self {
+        Self {
+            weights: Vec::new(),
+            delays: Vec::new(),
+            kinds: Vec::new(),
+            source_offsets: vec![0],
+            sources: Vec::new(),
+            target_offsets: vec![0],
+            targets: Vec::new(),
+            out_offsets: Vec::new(),
+            out_edges: Vec::new(),
+            in_offsets: Vec::new(),
+            in_edges: Vec::new(),
+            out_target_offsets: vec![0],
+            out_targets: Vec::new(),
+            compiled: true,
+        }
+    }
+
+    /// Compiled copy of `edges` (ids are reassigned by position).
+    pub fn from_edges(edges: &[HyperEdge]) -> Self {
+        let mut arena = Self::new();
+        for e in edges {
+            arena.push(&e.sources, &e.targets, e.weight, e.delay, e.kind);
+        }
+        arena.compile();
+        arena
+    }
+
+    /// Append an edge; returns its id. Leaves the arena uncompiled.
+    pub fn push(&mut self, sources: &[NeuronId], targets: &[NeuronId], weight: Fixed, delay: u64, kind: EdgeKind) -> u32 {
+        let id = self.weights.len() as u32;
+        self.weights.push(weight);
+        self.delays.push(delay);
+        self.kinds.push(kind);
+        self.sources.extend_from_slice(sources);
+        self.source_offsets.push(self.sources.len() as u32);
+        self.targets.extend_from_slice(targets);
+        self.target_offsets.push(self.targets.len() as u32);
+        self.compiled = false;
+        id
+    }
+
+    /// Remove the given edges (any order; unknown ids are ignored) and renumber the rest
+    /// (id == index, order kept). Returns the removed edges with their old ids, in ascending
+    /// id order. Keeps the arena compiled if it was.
+    pub fn remove(&mut self, ids: &[u32]) -> Vec<HyperEdge> {
+        let mut removed = Vec::new();
+        if ids.is_empty() {
+            return removed;
+        }
+        let mut ids = ids.to_vec();
+        ids.sort_unstable();
+        ids.dedup();
+        let old = core::mem::take(self);
+        for edge in old.iter() {
+            if ids.binary_search(&edge.id).is_ok() {
+                removed.push(edge.to_edge());
+            } else {
+                self.push(edge.sources, edge.targets, edge.weight, edge.delay, edge.kind);
+            }
+        }
+        if old.compiled {
+            self.compile();
+        }
+        removed
+    }
+
+    /// Rebuild the source -> edges and target -> edges indexes; no-op when they are current.
+    pub fn compile(&mut self) {
+        if self.compiled {
+            return;
+        }
+        (self.out_offsets, self.out_edges) = index_by_member(&self.source_offsets, &self.sources);
+        (self.in_offsets, self.in_edges) = index_by_member(&self.target_offsets, &self.targets);
+        let mut out_targets = Vec::new();
+        let mut out_target_offsets = Vec::with_capacity(self.out_edges.len() + 1);
+        out_target_offsets.push(0);
+        for &id in &self.out_edges {
+            out_targets.extend_from_slice(self.targets(id));
+            out_target_offsets.push(out_targets.len() as u32);
+        }
+        (self.out_target_offsets, self.out_targets) = (out_target_offsets, out_targets);
+        self.compiled = true;
+    }
+
+    /// False after `push` until the next `compile`.
+    pub fn is_compiled(&self) -> bool {
+        self.compiled
+    }
+
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    #[inline]
+    pub fn sources(&self, id: u32) -> &[NeuronId] {
+        let e = id as usize;
+        &self.sources[self.source_offsets[e] as usize..self.source_offsets[e + 1] as usize]
+    }
+
+    #[inline]
+    pub fn targets(&self, id: u32) -> &[NeuronId] {
+        let e = id as usize;
+        &self.targets[self.target_offsets[e] as usize..self.target_offsets[e + 1] as usize]
+    }
+
+    #[inline]
+    pub fn weight(&self, id: u32) -> Fixed {
+        self.weights[id as usize]
+    }
+
+    #[inline]
+    pub fn delay(&self, id: u32) -> u64 {
+        self.delays[id as usize]
+    }
+
+    #[inline]
+    pub fn kind(&self, id: u32) -> EdgeKind {
+        self.kinds[id as usize]
+    }
+
+    /// Weights by edge id, for in-place updates (topology stays frozen).
+    pub fn weights_mut(&mut self) -> &mut [Fixed] {
+        &mut self.weights
+    }
+
+    /// Clamp every weight to its kind's sign (Dale's law).
+    pub fn clamp_to_kinds(&mut self) {
+        for (w, kind) in self.weights.iter_mut().zip(&self.kinds) {
+            *w = kind.clamp(*w);
+        }
+    }
+
+    /// Outgoing edge ids of `neuron` in ascending order. Only valid while compiled.
+    #[inline]
+    pub fn outgoing(&self, neuron: NeuronId) -> &[u32] {
+        debug_assert!(self.compiled, "EdgeArena::outgoing before compile");
+        neighbours(&self.out_offsets, &self.out_edges, neuron)
+    }
+
+    /// Outgoing edges of `neuron` in ascending id order, for spike delivery: the targets come
+    /// from the per-source copy, so consecutive edges' targets are adjacent in memory. Only
+    /// valid while compiled.
+    #[inline]
+    pub fn fan_out(&self, neuron: NeuronId) -> impl Iterator<Item = OutEdge<'_>> + '_ {
+        debug_assert!(self.compiled, "EdgeArena::fan_out before compile");
+        let n = neuron as usize;
+        let slots = match (self.out_offsets.get(n), self.out_offsets.get(n + 1)) {
+            (Some(&a), Some(&b)) => a as usize..b as usize,
+            _ => 0..0,
+        };
+        slots.map(move |k| self.out_slot(k))
+    }
+
+    /// Entry `pos` of `fan_out(neuron)` (the edge `outgoing(neuron)[pos]`); panics if there
+    /// is none. Only valid while compiled.
+    #[inline]
+    pub fn fan_out_at(&self, neuron: NeuronId, pos: usize) -> OutEdge<'_> {
+        debug_assert!(pos < self.outgoing(neuron).len(), "EdgeArena::fan_out_at past the fan-out");
+        self.out_slot(self.out_offsets[neuron as usize] as usize + pos)
+    }
+
+    #[inline]
+    fn out_slot(&self, k: usize) -> OutEdge<'_> {
+        let id = self.out_edges[k];
+        let e = id as usize;
+        OutEdge {
+            id,
+            targets: &self.out_targets[self.out_target_offsets[k] as usize..self.out_target_offsets[k + 1] as usize],
+            weight: self.weights[e],
+            delay: self.delays[e],
+            kind: self.kinds[e],
+        }
+    }
+
+    /// Incoming edge ids of `neuron` in ascending order. Only valid while compiled.
+    #[inline]
+    pub fn incoming(&self, neuron: NeuronId) -> &[u32] {
+        debug_assert!(self.compiled, "EdgeArena::incoming before compile");
+        neighbours(&self.in_offsets, &self.in_edges, neuron)
+    }
+
+    /// View of edge `id`; panics if there is none (see `get`).
+    pub fn edge(&self, id: u32) -> EdgeView<'_> {
+        EdgeView {
+            id,
+            sources: self.sources(id),
+            targets: self.targets(id),
+            weight: self.weight(id),
+            delay: self.delay(id),
+            kind: self.kind(id),
+        }
+    }
+
+    pub fn get(&self, id: u32) -> Option<EdgeView<'_>> {
+        ((id as usize) < self.len()).then(|| self.edge(id))
+    }
+
+    /// Mutable view of edge `id`; panics if there is none.
+    pub fn edge_mut(&mut self, id: u32) -> EdgeViewMut<'_> {
+        let e = id as usize;
+        EdgeViewMut {
+            id,
+            sources: &self.sources[self.source_offsets[e] as usize..self.source_offsets[e + 1] as usize],
+            targets: &self.targets[self.target_offsets[e] as usize..self.target_offsets[e + 1] as usize],
+            weight: &mut self.weights[e],
+            delay: self.delays[e],
+            kind: self.kinds[e],
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = EdgeView<'_>> + '_ {
+        (0..self.len() as u32).map(|id| self.edge(id))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = EdgeViewMut<'_>> + '_ {
+        let Self { weights, delays, kinds, source_offsets, sources, target_offsets, targets, .. } = self;
+        let (sources, targets) = (&*sources, &*targets);
+        weights.iter_mut().enumerate().map(move |(e, weight)| EdgeViewMut {
+            id: e as u32,
+            sources: &sources[source_offsets[e] as usize..source_offsets[e + 1] as usize],
+            targets: &targets[target_offsets[e] as usize..target_offsets[e + 1] as usize],
+            weight,
+            delay: delays[e],
+            kind: kinds[e],
+        })
+    }
+
+    /// Owned edges in the `HyperEdge` layout.
+    pub fn to_edges(&self) -> Vec<HyperEdge> {
+        self.iter().map(|v| v.to_edge()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_fixed;
+
+    #[test]
+    fn test_edge_arena_views_and_adjacency() {
+        let edges = vec![
+            HyperEdge { id: 0, sources: vec![0, 1], targets: vec![2], weight: to_fixed(0.5), delay: 1, kind: EdgeKind::Excitatory },
+            HyperEdge { id: 1, sources: vec![1], targets: vec![0, 2], weight: to_fixed(-0.25), delay: 3, kind: EdgeKind::Inhibitory },
+        ];
+        let mut arena = EdgeArena::from_edges(&edges);
+        assert!(arena.is_compiled());
+        assert_eq!(arena.outgoing(0), &[0]);
+        assert_eq!(arena.outgoing(1), &[0, 1]);
+        assert_eq!(arena.incoming(2), &[0, 1]);
+        assert!(arena.outgoing(3).is_empty() && arena.outgoing(99).is_empty());
+        let view = arena.edge(1);
+        assert_eq!((view.sources, view.targets, view.delay), (&[1][..], &[0, 2][..], 3));
+        assert_eq!(arena.to_edges(), edges);
+
+        // Weights change in place; the kind's sign can be restored
+        *arena.edge_mut(1).weight = to_fixed(0.5);
+        arena.edge_mut(1).clamp_to_kind();
+        assert_eq!(arena.weight(1), 0);
+
+        // Incremental push leaves the indexes to the next compile
+        let id = arena.push(&[3], &[1], to_fixed(1.0), 2, EdgeKind::Excitatory);
+        assert_eq!(id, 2);
+        assert!(!arena.is_compiled());
+        arena.compile();
+        assert_eq!(arena.outgoing(3), &[2]);
+        let fan: Vec<(u32, &[NeuronId], u64)> = arena.fan_out(1).map(|e| (e.id, e.targets, e.delay)).collect();
+        assert_eq!(fan, vec![(0, &[2][..], 1), (1, &[0, 2][..], 3)]);
+
+        // Removal renumbers the rest and keeps the indexes current
+        let removed = arena.remove(&[0]);
+        assert_eq!(removed[0].id, 0);
+        assert_eq!((arena.len(), arena.outgoing(3), arena.incoming(1)), (2, &[1][..], &[1][..]));
+        assert!(arena.get(2).is_none());
+
+        // Ids may come unsorted and repeated; unknown ones are ignored
+        let removed: Vec<u32> = arena.remove(&[1, 7, 0, 1]).iter().map(|e| e.id).collect();
+        assert_eq!((removed, arena.len()), (vec![0, 1], 0));
+    }
+}
@@ -1,15 +1,119 @@
 //! Time wheel (calendar queue) and spike event
 
-#[derive(Clone, Copy, Debug)]
+use core::ops::Range;
+
+use crate::fixed::Fixed;
+use crate::neuron::{NeuronId, NeuronParam};
+
+/// Payload of a scheduled event. Everything except `Spike` is a control event: it is applied
+/// to its neuron (or the whole network) at the start of its tick, before that tick's spikes
+/// are delivered, so experiment protocols can be scheduled up front and replayed exactly.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EventKind {
+    /// The neuron spiked; its outgoing edges deliver
+    #[default]
+    Spike,
+    /// Inject current directly into the neuron (fixed-point); a threshold crossing spikes on
+    /// the next tick
+    Current(Fixed),
+    /// Set a neuron parameter to a value (fixed-point)
+    SetParam(NeuronParam, Fixed),
+    /// Neuromodulator / reward pulse for plasticity rules (fixed-point)
+    Reward(Fixed),
+    /// User marker with no effect, returned from the step like any other event
+    Marker(u32),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct SpikeEvent {
-    pub neuron_id: u32,
+    pub neuron_id: NeuronId,
     pub time: u64,
+    /// Position inside tick `time` in 1/65536 tick units (0 = start of the tick). The wheel
+    /// buckets by `time` only; the offset rides along through edge delays. It fills padding
+    /// after `neuron_id`, so the event stays 24 bytes as before offsets existed.
+    pub offset: u16,
+    pub kind: EventKind,
+}
+
+impl SpikeEvent {
+    /// `neuron_id` of events that target the whole network (rewards, markers)
+    pub const NO_NEURON: NeuronId = NeuronId::MAX;
+
+    pub const fn spike(neuron_id: NeuronId, time: u64) -> Self {
+        Self { neuron_id, time, offset: 0, kind: EventKind::Spike }
+    }
+
+    pub const fn current(neuron_id: NeuronId, time: u64, amount: Fixed) -> Self {
+        Self { neuron_id, time, offset: 0, kind: EventKind::Current(amount) }
+    }
+
+    pub const fn set_param(neuron_id: NeuronId, time: u64, param: NeuronParam, value: Fixed) -> Self {
+        Self { neuron_id, time, offset: 0, kind: EventKind::SetParam(param, value) }
+    }
+
+    pub const fn reward(time: u64, amount: Fixed) -> Self {
+        Self { neuron_id: Self::NO_NEURON, time, offset: 0, kind: EventKind::Reward(amount) }
+    }
+
+    pub const fn marker(time: u64, id: u32) -> Self {
+        Self { neuron_id: Self::NO_NEURON, time, offset: 0, kind: EventKind::Marker(id) }
+    }
+
+    /// The same event at a sub-tick offset (1/65536 tick units).
+    pub const fn with_offset(self, offset: u16) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// Time in ticks including the sub-tick offset.
+    pub fn exact_time(&self) -> f64 {
+        self.time as f64 + f64::from(self.offset) / 65536.0
+    }
+
+    #[inline]
+    pub fn is_spike(&self) -> bool {
+        self.kind == EventKind::Spike
+    }
 }
 
+/// Order in which `TimeWheel::next` returns the events of one tick.
+/// Budgeted stepping processes events in this order, so it also decides which spikes are
+/// delivered before a budget cuts off.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EventOrder {
+    /// Scheduling order (first scheduled, first returned)
+    #[default]
+    Fifo,
+    /// Ascending neuron id, independent of scheduling order
+    NeuronId,
+    /// Descending priority class (higher is more urgent), then ascending neuron id; classes
+    /// are set per neuron with `set_priority` (default 0)
+    Priority,
+    /// Ascending sub-tick offset, then ascending neuron id: immediate delivery then sees a
+    /// tick's spikes in the order they happened
+    Time,
+}
+
+/// Why `TimeWheel::schedule` rejected an event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// `time` is before the wheel's current time (that tick was already popped)
+    Past { time: u64, current_time: u64 },
+    /// `time` is more than one revolution ahead; `horizon` is the last schedulable tick
+    BeyondHorizon { time: u64, horizon: u64 },
+}
+
+#[derive(Clone)]
 pub struct TimeWheel {
     buckets: Vec<Vec<SpikeEvent>>,
     pub current_time: u64,
     wheel_size: u64,
+    order: EventOrder,
+    // Priority class per neuron id (missing = 0)
+    priorities: Vec<u8>,
+    // Number of scheduled events across all buckets (occupancy)
+    len: usize,
+    // Emptied bucket handed back by `recycle`, swapped in by `next` so buckets keep capacity
+    spare: Vec<SpikeEvent>,
 }
 
 impl TimeWheel {
@@ -22,20 +126,243 @@ impl TimeWheel {
             buckets,
             current_time: 0,
             wheel_size,
+            order: EventOrder::Fifo,
+            priorities: Vec::new(),
+            len: 0,
+            spare: Vec::new(),
+        }
+    }
+
+    pub fn wheel_size(&self) -> u64 {
+        self.wheel_size
+    }
+
+    /// Enlarge the wheel to `wheel_size` slots (no-op unless larger). Pending events keep
+    /// their times and, within a tick, their order; the horizon moves out accordingly.
+    pub fn grow(&mut self, wheel_size: u64) {
+        if wheel_size <= self.wheel_size {
+            return;
         }
+        let mut old = core::mem::take(&mut self.buckets);
+        let old_size = self.wheel_size;
+        self.buckets = (0..wheel_size).map(|_| Vec::new()).collect();
+        self.wheel_size = wheel_size;
+        // Pending times span [current_time, old horizon]: one tick per old slot
+        for t in self.current_time..self.current_time.saturating_add(old_size) {
+            let bucket = core::mem::take(&mut old[(t % old_size) as usize]);
+            if !bucket.is_empty() {
+                self.buckets[(t % wheel_size) as usize] = bucket;
+            }
+        }
+    }
+
+    /// Last tick `schedule` accepts: one revolution ahead of current_time, minus one.
+    pub fn horizon(&self) -> u64 {
+        self.current_time.saturating_add(self.wheel_size - 1)
+    }
+
+    /// Number of scheduled events.
+    pub fn pending_count(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// First tick >= current_time whose slot holds events (what a later `next` will return
+    /// first), or None if the wheel is empty. Scans at most one revolution.
+    pub fn next_event_time(&self) -> Option<u64> {
+        if self.len == 0 {
+            return None;
+        }
+        (0..self.wheel_size)
+            .map(|offset| self.current_time.saturating_add(offset))
+            .find(|&t| !self.buckets[(t % self.wheel_size) as usize].is_empty())
+    }
+
+    /// Jump current_time forward to `time` without popping, stopping early at the next
+    /// scheduled event so none is skipped. Returns the new current_time.
+    pub fn skip_to(&mut self, time: u64) -> u64 {
+        let target = self.next_event_time().map_or(time, |t| t.min(time));
+        self.current_time = self.current_time.max(target);
+        self.current_time
+    }
+
+    pub fn set_order(&mut self, order: EventOrder) {
+        self.order = order;
     }
 
+    pub fn order(&self) -> EventOrder {
+        self.order
+    }
+
+    /// Set the priority class of a neuron's events (used by `EventOrder::Priority`).
+    pub fn set_priority(&mut self, neuron_id: NeuronId, class: u8) {
+        let idx = neuron_id as usize;
+        if self.priorities.len() <= idx {
+            self.priorities.resize(idx + 1, 0);
+        }
+        self.priorities[idx] = class;
+    }
+
+    #[inline]
+    pub fn priority(&self, neuron_id: NeuronId) -> u8 {
+        *self.priorities.get(neuron_id as usize).unwrap_or(&0)
+    }
+
+    /// Schedule an event at `event.time`, which must lie in [current_time, horizon()].
+    /// Earlier times were already popped and later ones would alias a nearer slot.
     #[inline]
-    pub fn schedule(&mut self, event: SpikeEvent) {
+    pub fn schedule(&mut self, event: SpikeEvent) -> Result<(), ScheduleError> {
+        if event.time < self.current_time {
+            return Err(ScheduleError::Past { time: event.time, current_time: self.current_time });
+        }
+        if event.time > self.horizon() {
+            return Err(ScheduleError::BeyondHorizon { time: event.time, horizon: self.horizon() });
+        }
         let slot = (event.time % self.wheel_size) as usize;
         self.buckets[slot].push(event);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Like `schedule`, but an event in the past is moved to current_time (the earliest tick
+    /// not yet popped) instead of being rejected. Returns the tick it was scheduled at.
+    pub fn schedule_clamped(&mut self, mut event: SpikeEvent) -> Result<u64, ScheduleError> {
+        event.time = event.time.max(self.current_time);
+        self.schedule(event)?;
+        Ok(event.time)
     }
 
-    /// Return all events scheduled at the current time slot, then advance time by 1 tick.
+    /// Events of the current slot (what `next` returns), in scheduling order.
+    pub fn peek_current(&self) -> &[SpikeEvent] {
+        &self.buckets[(self.current_time % self.wheel_size) as usize]
+    }
+
+    /// Remove all pending events, keeping bucket capacity and current_time.
+    pub fn clear(&mut self) {
+        self.buckets.iter_mut().for_each(Vec::clear);
+        self.len = 0;
+    }
+
+    /// Remove every pending event of `neuron_id`. Returns how many were removed.
+    pub fn cancel(&mut self, neuron_id: NeuronId) -> usize {
+        let before = self.len;
+        for bucket in &mut self.buckets {
+            bucket.retain(|e| e.neuron_id != neuron_id);
+        }
+        self.len = self.buckets.iter().map(Vec::len).sum();
+        before - self.len
+    }
+
+    /// Remove and return the pending events with time in `range`, by ascending time (each
+    /// tick in the configured `EventOrder`). Does not move current_time.
+    pub fn drain_range(&mut self, range: Range<u64>) -> Vec<SpikeEvent> {
+        let start = range.start.max(self.current_time);
+        let end = range.end.min(self.horizon().saturating_add(1));
+        let mut out = Vec::new();
+        for t in start..end {
+            let bucket = &mut self.buckets[(t % self.wheel_size) as usize];
+            let from = out.len();
+            out.append(bucket);
+            self.sort_events(&mut out[from..]);
+        }
+        self.len -= out.len();
+        out
+    }
+
+    /// Hand back an events buffer returned by `next` once it has been processed; `next` reuses
+    /// its allocation for the emptied slot, so steady-state stepping does not allocate.
+    pub fn recycle(&mut self, mut events: Vec<SpikeEvent>) {
+        if events.capacity() > self.spare.capacity() {
+            events.clear();
+            self.spare = events;
+        }
+    }
+
+    /// Return all events scheduled at the current time slot in the configured `EventOrder`,
+    /// then advance time by 1 tick.
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Vec<SpikeEvent> {
+        let mut events = core::mem::take(&mut self.spare);
+        self.next_into(&mut events);
+        events
+    }
+
+    /// Like `next`, but swaps the slot's events into `out` (cleared first); the slot keeps
+    /// `out`'s previous allocation.
+    pub fn next_into(&mut self, out: &mut Vec<SpikeEvent>) {
+        out.clear();
         let slot = (self.current_time % self.wheel_size) as usize;
-        let events = core::mem::take(&mut self.buckets[slot]);
+        core::mem::swap(out, &mut self.buckets[slot]);
+        self.len -= out.len();
+        self.sort_events(out);
         self.current_time = self.current_time.saturating_add(1);
-        events
     }
-}
\ No newline at end of file
+
+    // Stable sorts: equal keys keep scheduling order
+    fn sort_events(&self, events: &mut [SpikeEvent]) {
+        match self.order {
+            EventOrder::Fifo => {}
+            EventOrder::NeuronId => events.sort_by_key(|e| e.neuron_id),
+            EventOrder::Priority => events.sort_by_key(|e| (core::cmp::Reverse(self.priority(e.neuron_id)), e.neuron_id)),
+            EventOrder::Time => events.sort_by_key(|e| (e.offset, e.neuron_id)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spike_event_offset_fits_in_padding() {
+        // time (8) + neuron_id (4 or 2) + kind (8) leave room for the u16 offset
+        assert_eq!(core::mem::size_of::<SpikeEvent>(), 24);
+    }
+
+    #[test]
+    fn test_wheel_rejects_past_and_beyond_horizon() {
+        let mut q = TimeWheel::new(8);
+        q.schedule(SpikeEvent::spike(0, 2)).unwrap();
+        q.next();
+        q.next();
+        assert_eq!(q.schedule(SpikeEvent::spike(1, 1)), Err(ScheduleError::Past { time: 1, current_time: 2 }));
+        assert_eq!(q.schedule(SpikeEvent::spike(1, 10)), Err(ScheduleError::BeyondHorizon { time: 10, horizon: 9 }));
+        assert_eq!(q.schedule_clamped(SpikeEvent::spike(1, 0)), Ok(2));
+        assert_eq!(q.schedule(SpikeEvent::spike(2, 9)), Ok(()));
+        assert_eq!(q.pending_count(), 3);
+        assert_eq!(q.peek_current().len(), 2);
+    }
+
+    #[test]
+    fn test_wheel_cancel_and_drain_range() {
+        let mut q = TimeWheel::new(16);
+        q.set_order(EventOrder::NeuronId);
+        for (n, t) in [(3, 1), (1, 1), (2, 4), (1, 6), (2, 9)] {
+            q.schedule(SpikeEvent::spike(n, t)).unwrap();
+        }
+        assert_eq!(q.cancel(2), 2);
+        assert_eq!(q.pending_count(), 3);
+        let drained: Vec<(NeuronId, u64)> = q.drain_range(0..6).iter().map(|e| (e.neuron_id, e.time)).collect();
+        assert_eq!(drained, vec![(1, 1), (3, 1)]);
+        assert_eq!(q.pending_count(), 1);
+        assert_eq!(q.next_event_time(), Some(6));
+    }
+
+    #[test]
+    fn test_next_into_reuses_bucket_buffers() {
+        let mut q = TimeWheel::new(4);
+        let mut buf = Vec::with_capacity(32);
+        let ptr = buf.as_ptr();
+        // The buffer handed in at t=0 becomes slot 0's bucket and comes back one revolution later
+        for t in 0..5 {
+            q.schedule(SpikeEvent::spike(0, t)).unwrap();
+            q.next_into(&mut buf);
+            assert_eq!(buf.len(), 1);
+        }
+        assert_eq!(buf.as_ptr(), ptr);
+        assert_eq!(q.pending_count(), 0);
+    }
+}
@@ -17,4 +17,19 @@ pub fn from_fixed(x: Fixed) -> f32 {
 #[inline]
 pub fn fixed_mul(a: Fixed, b: Fixed) -> Fixed {
     ((a as i64 * b as i64) >> FRACTIONAL_BITS) as Fixed
-}
\ No newline at end of file
+}
+
+/// base^exp in fixed-point by repeated squaring; with base = exp(-1/tau) this is the exact
+/// decay factor over `exp` ticks.
+#[inline]
+pub fn fixed_pow(mut base: Fixed, mut exp: u64) -> Fixed {
+    let mut acc = SCALE;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = fixed_mul(acc, base);
+        }
+        base = fixed_mul(base, base);
+        exp >>= 1;
+    }
+    acc
+}
@@ -1,9 +1,94 @@
 //! Hypergraph connectivity: hyperedges connect multiple sources to multiple targets.
 
+use crate::fixed::Fixed;
+use crate::neuron::NeuronId;
+
+/// What a neuron's target does with a modulatory input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Modulation {
+    /// Add the weight to the target's input gain (gain is floored at 0)
+    Gain,
+    /// Add the weight to the target's threshold
+    Threshold,
+}
+
+/// Type of a hyperedge.
+/// Excitatory/inhibitory edges inject current; modulatory edges change a property of
+/// their targets instead and never make them fire.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeKind {
+    Excitatory,
+    Inhibitory,
+    Modulatory(Modulation),
+}
+
+impl EdgeKind {
+    /// Excitatory for non-negative weights, inhibitory otherwise.
+    pub fn from_weight(weight: Fixed) -> Self {
+        if weight < 0 {
+            EdgeKind::Inhibitory
+        } else {
+            EdgeKind::Excitatory
+        }
+    }
+
+    /// Sign the weight must have (+1, -1), or 0 when unconstrained.
+    pub fn sign(&self) -> i32 {
+        match self {
+            EdgeKind::Excitatory => 1,
+            EdgeKind::Inhibitory => -1,
+            EdgeKind::Modulatory(_) => 0,
+        }
+    }
+
+    /// True when `weight`'s sign agrees with the kind (zero is always allowed).
+    pub fn admits(&self, weight: Fixed) -> bool {
+        match self.sign() {
+            1 => weight >= 0,
+            -1 => weight <= 0,
+            _ => true,
+        }
+    }
+
+    /// `weight` clamped to the sign required by the kind.
+    pub fn clamp(&self, weight: Fixed) -> Fixed {
+        match self.sign() {
+            1 => weight.max(0),
+            -1 => weight.min(0),
+            _ => weight,
+        }
+    }
+}
+
+/// Network validation failure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeError {
+    /// The weight's sign contradicts the edge kind
+    SignMismatch { edge: u32 },
+    /// A neuron has both excitatory and inhibitory outgoing edges (Dale's law)
+    DaleViolation { neuron: NeuronId, edge: u32 },
+    /// The delay exceeds the time wheel size, so its spikes cannot be scheduled
+    DelayBeyondHorizon { edge: u32, delay: u64 },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HyperEdge {
     pub id: u32,
-    pub sources: Vec<u32>,
-    pub targets: Vec<u32>,
+    pub sources: Vec<NeuronId>,
+    pub targets: Vec<NeuronId>,
     pub weight: i32, // fixed-point
     pub delay: u64,  // ticks
-}
\ No newline at end of file
+    pub kind: EdgeKind,
+}
+
+impl HyperEdge {
+    /// True when the weight's sign agrees with the kind (zero is always allowed).
+    pub fn sign_ok(&self) -> bool {
+        self.kind.admits(self.weight)
+    }
+
+    /// Clamp the weight to the sign required by the kind (e.g. after plasticity).
+    pub fn clamp_to_kind(&mut self) {
+        self.weight = self.kind.clamp(self.weight);
+    }
+}
@@ -12,6 +12,12 @@ pub struct Program {
     pub ops: Vec<SnnOp>,
 }
 
+impl Default for Program {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Program {
     pub fn new() -> Self {
         Self { ops: Vec::new() }
@@ -7,12 +7,18 @@ pub mod ir;
 pub mod neuron;
 pub mod hypergraph;
 pub mod runtime;
+pub mod time;
+pub mod stop;
+pub mod arena;
 
 // Re-exports
-pub use event_queue::{SpikeEvent, TimeWheel};
-pub use fixed::{Fixed, FRACTIONAL_BITS, SCALE, to_fixed, from_fixed, fixed_mul};
+pub use event_queue::{EventKind, EventOrder, ScheduleError, SpikeEvent, TimeWheel};
+pub use fixed::{Fixed, FRACTIONAL_BITS, SCALE, to_fixed, from_fixed, fixed_mul, fixed_pow};
 pub use sparse::CsrMatrix;
 pub use ir::{SnnOp, Program};
-pub use neuron::Neuron;
-pub use hypergraph::HyperEdge;
-pub use runtime::SnnRuntime;
\ No newline at end of file
+pub use neuron::{neuron_id, Neuron, NeuronId, NeuronParam};
+pub use hypergraph::{EdgeError, EdgeKind, HyperEdge, Modulation};
+pub use runtime::{InputAccumulator, SnnRuntime, StepMode, AUTO_WHEEL_SIZE};
+pub use time::{RoundingWarning, TimeBase};
+pub use stop::{RunSummary, StopCondition, StopTracker};
+pub use arena::{EdgeArena, EdgeView, EdgeViewMut, OutEdge};
\ No newline at end of file
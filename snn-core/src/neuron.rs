@@ -1,21 +1,46 @@
 //! Spiking neuron with fixed-point membrane potential
 
-use crate::fixed::{Fixed, to_fixed};
+use crate::fixed::{Fixed, SCALE, fixed_mul, to_fixed};
+use crate::hypergraph::Modulation;
 
+/// Neuron state a scheduled `EventKind::SetParam` can overwrite.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NeuronParam {
+    Membrane,
+    Threshold,
+    Gain,
+}
+
+/// Neuron id: `u32`, or `u16` with feature "id16" (networks of up to 65 535 neurons;
+/// halves id memory in events, edges and adjacency, and matches the embedded modules).
+#[cfg(not(feature = "id16"))]
+pub type NeuronId = u32;
+#[cfg(feature = "id16")]
+pub type NeuronId = u16;
+
+/// Id of the neuron at `index`; panics when the id width cannot represent it.
+pub fn neuron_id(index: usize) -> NeuronId {
+    NeuronId::try_from(index).expect("neuron index exceeds the NeuronId width (feature \"id16\")")
+}
+
+#[derive(Clone, Debug)]
 pub struct Neuron {
-    pub id: u32,
+    pub id: NeuronId,
     pub membrane: Fixed,
     pub threshold: Fixed,
     pub refractory_until: u64,
+    /// Multiplier on injected input (1.0 by default), changed by modulatory edges
+    pub gain: Fixed,
 }
 
 impl Neuron {
-    pub fn new(id: u32, threshold: f32) -> Self {
+    pub fn new(id: NeuronId, threshold: f32) -> Self {
         Self {
             id,
             membrane: 0,
             threshold: to_fixed(threshold),
             refractory_until: 0,
+            gain: SCALE,
         }
     }
 
@@ -25,6 +50,7 @@ impl Neuron {
         if time < self.refractory_until {
             return false;
         }
+        let input = if self.gain == SCALE { input } else { fixed_mul(input, self.gain) };
         // Simple integrate-and-fire without leak
         self.membrane = self.membrane.saturating_add(input);
         if self.membrane >= self.threshold {
@@ -35,4 +61,37 @@ impl Neuron {
         }
         false
     }
-}
\ No newline at end of file
+
+    /// Inject input that arrives spread evenly over the tick (e.g. a synaptic current).
+    /// On fire, returns the interpolated crossing point inside the tick in 1/65536 tick
+    /// units: the fraction of the input that was needed to reach threshold.
+    pub fn inject_spread(&mut self, input: Fixed, time: u64) -> Option<u16> {
+        let before = self.membrane;
+        if !self.inject(input, time) {
+            return None;
+        }
+        let input = if self.gain == SCALE { input } else { fixed_mul(input, self.gain) };
+        let needed = i64::from(self.threshold) - i64::from(before);
+        if input <= 0 || needed <= 0 {
+            return Some(0);
+        }
+        Some(((needed << 16) / i64::from(input)).min(u16::MAX as i64) as u16)
+    }
+
+    /// Overwrite a parameter (gain is floored at 0, like `modulate`).
+    pub fn set_param(&mut self, param: NeuronParam, value: Fixed) {
+        match param {
+            NeuronParam::Membrane => self.membrane = value,
+            NeuronParam::Threshold => self.threshold = value,
+            NeuronParam::Gain => self.gain = value.max(0),
+        }
+    }
+
+    /// Apply a modulatory input: shifts gain or threshold, never fires the neuron.
+    pub fn modulate(&mut self, modulation: Modulation, amount: Fixed) {
+        match modulation {
+            Modulation::Gain => self.gain = self.gain.saturating_add(amount).max(0),
+            Modulation::Threshold => self.threshold = self.threshold.saturating_add(amount),
+        }
+    }
+}
@@ -1,67 +1,330 @@
 //! SNN runtime: manages neurons, hyperedges, and event-driven execution via time wheel.
 
-use crate::{Neuron, HyperEdge, SpikeEvent, TimeWheel, to_fixed};
+use std::sync::Arc;
 
+use crate::arena::EdgeArena;
+use crate::{EdgeError, EdgeKind, EventKind, Fixed, Neuron, NeuronId, RoundingWarning, RunSummary, SpikeEvent, StopCondition, StopTracker, TimeBase, TimeWheel, neuron_id, to_fixed};
+
+/// How deliveries within one tick update their targets.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StepMode {
+    /// Each delivery is injected (and may fire/reset the target) as it happens; results
+    /// depend on edge order.
+    #[default]
+    Immediate,
+    /// All inputs of the tick are summed per (delivery time, target) first; threshold, reset
+    /// and spike scheduling then run once per target in (time, id) order. Results do not
+    /// depend on edge or adjacency order. Modulatory inputs apply before any current. Sums
+    /// ignore sub-tick offsets, so its spikes start at their tick (offset 0).
+    TwoPhase,
+}
+
+/// Per-tick input accumulator for `StepMode::TwoPhase`; keeps its allocation across ticks.
+#[derive(Clone, Default)]
+pub struct InputAccumulator {
+    pending: Vec<(u64, NeuronId, Fixed)>,
+}
+
+impl InputAccumulator {
+    /// Add `input` for `target` delivered at `time`.
+    #[inline]
+    pub fn add(&mut self, time: u64, target: NeuronId, input: Fixed) {
+        self.pending.push((time, target, input));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Sum the inputs per (time, target) and return them in ascending (time, target) order.
+    /// Sums are exact (i64) before saturating to Fixed, so the result is order-independent.
+    pub fn drain_sorted(&mut self) -> Vec<(u64, NeuronId, Fixed)> {
+        self.pending.sort_unstable_by_key(|&(t, n, _)| (t, n));
+        let mut out: Vec<(u64, NeuronId, Fixed)> = Vec::new();
+        let mut sum: i64 = 0;
+        for (i, &(t, n, w)) in self.pending.iter().enumerate() {
+            sum += w as i64;
+            let last = self.pending.get(i + 1).is_none_or(|&(t2, n2, _)| (t2, n2) != (t, n));
+            if last {
+                out.push((t, n, sum.clamp(i32::MIN as i64, i32::MAX as i64) as Fixed));
+                sum = 0;
+            }
+        }
+        self.pending.clear();
+        out
+    }
+}
+
+/// Network state. The edges live in an `EdgeArena` shared copy-on-write between forks:
+/// `edges_mut` copies it only while it is shared. Adding or removing edges leaves its
+/// source/target indexes stale until the next `compile`, which `step_once` runs itself.
+#[derive(Clone)]
 pub struct SnnRuntime {
     pub neurons: Vec<Neuron>,
-    pub edges: Vec<HyperEdge>,
+    edges: Arc<EdgeArena>,
     pub queue: TimeWheel,
+    pub step_mode: StepMode,
+    accumulator: InputAccumulator,
+    time_base: TimeBase,
+    // Millisecond values that did not map to whole ticks (see take_time_warnings)
+    time_warnings: Vec<RoundingWarning>,
+    // Longest delay added so far, and whether longer delays may grow the wheel
+    max_delay: u64,
+    wheel_growth: bool,
 }
 
+/// Initial wheel size of `SnnRuntime::auto_sized` (it grows with the longest delay).
+pub const AUTO_WHEEL_SIZE: u64 = 16;
+
 impl SnnRuntime {
+    /// Runtime whose wheel starts at `wheel_size` slots and grows when an edge needs a longer
+    /// delay (see `set_wheel_growth`).
     pub fn new(wheel_size: u64) -> Self {
         Self {
             neurons: Vec::new(),
-            edges: Vec::new(),
+            edges: Arc::default(),
             queue: TimeWheel::new(wheel_size),
+            step_mode: StepMode::Immediate,
+            accumulator: InputAccumulator::default(),
+            time_base: TimeBase::default(),
+            time_warnings: Vec::new(),
+            max_delay: 0,
+            wheel_growth: true,
+        }
+    }
+
+    /// Runtime that sizes its wheel from the network: it starts small and grows to fit the
+    /// longest edge delay.
+    pub fn auto_sized() -> Self {
+        Self::new(AUTO_WHEEL_SIZE)
+    }
+
+    /// Allow (default) or forbid growing the wheel for long delays. Without growth,
+    /// `try_add_typed_edge` rejects such edges and the plain `add_*` methods panic.
+    pub fn set_wheel_growth(&mut self, enabled: bool) {
+        self.wheel_growth = enabled;
+    }
+
+    pub fn wheel_growth(&self) -> bool {
+        self.wheel_growth
+    }
+
+    /// Longest edge delay added so far, in ticks.
+    pub fn max_delay(&self) -> u64 {
+        self.max_delay
+    }
+
+    /// Make sure the wheel can hold `delay` for edge `edge`: a spike delivered over it must
+    /// fit before the horizon, i.e. `delay <= wheel_size`. Grows the wheel (to the next power
+    /// of two) when allowed, otherwise returns `DelayBeyondHorizon`. `max_delay` is only
+    /// raised once the edge is added.
+    pub fn reserve_delay(&mut self, edge: u32, delay: u64) -> Result<(), EdgeError> {
+        if delay <= self.queue.wheel_size() {
+            return Ok(());
         }
+        if !self.wheel_growth {
+            return Err(EdgeError::DelayBeyondHorizon { edge, delay });
+        }
+        self.queue.grow(delay.checked_next_power_of_two().unwrap_or(delay));
+        Ok(())
+    }
+
+    /// Set the physical duration of one tick (default 1 ms). Affects later `*_ms` calls and
+    /// physical timestamps only; existing delays stay in ticks.
+    pub fn set_time_base(&mut self, time_base: TimeBase) {
+        self.time_base = time_base;
+    }
+
+    pub fn time_base(&self) -> TimeBase {
+        self.time_base
+    }
+
+    /// Current time (start of the next tick to run) in milliseconds.
+    pub fn now_ms(&self) -> f64 {
+        self.time_base.ticks_to_ms(self.queue.current_time)
+    }
+
+    /// Convert a delay in ms to ticks, recording a warning if it had to be rounded.
+    pub fn ms_to_ticks(&mut self, ms: f64) -> u64 {
+        let (ticks, warning) = self.time_base.ms_to_ticks(ms);
+        self.time_warnings.extend(warning);
+        ticks
+    }
+
+    /// Take the rounding warnings recorded by `*_ms` calls since the last call.
+    pub fn take_time_warnings(&mut self) -> Vec<RoundingWarning> {
+        core::mem::take(&mut self.time_warnings)
+    }
+
+    /// The edges (id == index). Replaces the public `edges: Vec<HyperEdge>` field of 0.1;
+    /// docs/USAGE.md maps the old field accesses onto the arena.
+    pub fn edges(&self) -> &EdgeArena {
+        &self.edges
+    }
+
+    /// Edges for modification; copies them first if a fork still shares them.
+    pub fn edges_mut(&mut self) -> &mut EdgeArena {
+        Arc::make_mut(&mut self.edges)
+    }
+
+    /// The shared edge handle, for borrows that must outlive `&self` (and `Arc::ptr_eq`
+    /// between forks). Holding a clone makes the next `edges_mut` copy the arena.
+    pub fn shared_edges(&self) -> &Arc<EdgeArena> {
+        &self.edges
+    }
+
+    /// Rebuild the source/target indexes after edges were added or removed, so each spike
+    /// visits only its outgoing edges. `step_once` does this on demand; calling it up front
+    /// keeps the cost out of the first tick.
+    pub fn compile(&mut self) {
+        if !self.edges.is_compiled() {
+            self.edges_mut().compile();
+        }
+    }
+
+    /// Clear the dynamic state and keep the network: membranes and refractory periods are
+    /// zeroed, pending events dropped and time rewound to 0. Thresholds, gains and weights
+    /// (including learned or modulated values) are kept.
+    pub fn reset_state(&mut self) {
+        for n in &mut self.neurons {
+            n.membrane = 0;
+            n.refractory_until = 0;
+        }
+        self.queue.clear();
+        self.queue.current_time = 0;
+        self.accumulator = InputAccumulator::default();
+    }
+
+    /// Copy of the runtime that shares the edge list with this one until either side
+    /// modifies it; neuron state and pending events are copied.
+    pub fn fork(&self) -> Self {
+        self.clone()
     }
 
-    pub fn add_neuron(&mut self, threshold: f32) -> u32 {
-        let id = self.neurons.len() as u32;
+    /// Select immediate or two-phase (order-independent) delivery.
+    pub fn set_step_mode(&mut self, mode: StepMode) {
+        self.step_mode = mode;
+    }
+
+    pub fn add_neuron(&mut self, threshold: f32) -> NeuronId {
+        let id = neuron_id(self.neurons.len());
         self.neurons.push(Neuron::new(id, threshold));
         id
     }
 
-    pub fn add_edge(&mut self, sources: Vec<u32>, targets: Vec<u32>, weight: f32, delay: u64) {
+    /// Add an edge whose kind follows the weight's sign (excitatory if >= 0).
+    pub fn add_edge(&mut self, sources: Vec<NeuronId>, targets: Vec<NeuronId>, weight: f32, delay: u64) {
+        let kind = EdgeKind::from_weight(to_fixed(weight));
+        self.add_typed_edge(sources, targets, weight, delay, kind);
+    }
+
+    /// `add_edge` with the delay in milliseconds (rounded to whole ticks); returns its id.
+    pub fn add_edge_ms(&mut self, sources: Vec<NeuronId>, targets: Vec<NeuronId>, weight: f32, delay_ms: f64) -> u32 {
+        let delay = self.ms_to_ticks(delay_ms);
+        let kind = EdgeKind::from_weight(to_fixed(weight));
+        self.add_typed_edge(sources, targets, weight, delay, kind)
+    }
+
+    /// Add an edge of an explicit kind; returns its id. Grows the wheel for a long delay.
+    /// Panics if the delay does not fit and growth is disabled; use `try_add_typed_edge` to
+    /// handle that case.
+    pub fn add_typed_edge(&mut self, sources: Vec<NeuronId>, targets: Vec<NeuronId>, weight: f32, delay: u64, kind: EdgeKind) -> u32 {
         let id = self.edges.len() as u32;
-        self.edges.push(HyperEdge {
-            id,
-            sources,
-            targets,
-            weight: to_fixed(weight),
-            delay,
-        });
+        if let Err(e) = self.reserve_delay(id, delay) {
+            panic!("{e:?} on a fixed-size wheel; use try_add_typed_edge to handle it");
+        }
+        self.max_delay = self.max_delay.max(delay);
+        self.edges_mut().push(&sources, &targets, to_fixed(weight), delay, kind)
+    }
+
+    /// `add_typed_edge` that adds nothing and returns `DelayBeyondHorizon` when the delay does
+    /// not fit the wheel and growth is disabled.
+    pub fn try_add_typed_edge(&mut self, sources: Vec<NeuronId>, targets: Vec<NeuronId>, weight: f32, delay: u64, kind: EdgeKind) -> Result<u32, EdgeError> {
+        self.reserve_delay(self.edges.len() as u32, delay)?;
+        Ok(self.add_typed_edge(sources, targets, weight, delay, kind))
+    }
+
+    /// Check that every delay fits the time wheel, every weight matches its edge kind and,
+    /// with `dales_law`, that all excitatory/inhibitory outgoing edges of a neuron share one sign.
+    pub fn validate(&self, dales_law: bool) -> Result<(), EdgeError> {
+        // Sign of the first typed outgoing edge seen per neuron (sized by the sources, so
+        // this also works when neurons are stored elsewhere, as in SnnRuntimePlus)
+        let mut signs: Vec<i32> = vec![0; self.neurons.len()];
+        for edge in self.edges.iter() {
+            if edge.delay > self.queue.wheel_size() {
+                return Err(EdgeError::DelayBeyondHorizon { edge: edge.id, delay: edge.delay });
+            }
+            if !edge.sign_ok() {
+                return Err(EdgeError::SignMismatch { edge: edge.id });
+            }
+            let sign = edge.kind.sign();
+            if !dales_law || sign == 0 {
+                continue;
+            }
+            for &s in edge.sources {
+                if signs.len() <= s as usize {
+                    signs.resize(s as usize + 1, 0);
+                }
+                let seen = &mut signs[s as usize];
+                if *seen == 0 {
+                    *seen = sign;
+                } else if *seen != sign {
+                    return Err(EdgeError::DaleViolation { neuron: s, edge: edge.id });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a control event (anything but `EventKind::Spike`) to its neuron. A current that
+    /// crosses threshold schedules a spike on the next tick. Rewards have no effect here
+    /// (snn-core has no plasticity); markers never do.
+    pub fn apply_control(&mut self, event: SpikeEvent) {
+        let Some(n) = self.neurons.get_mut(event.neuron_id as usize) else {
+            return;
+        };
+        match event.kind {
+            EventKind::Current(amount) => {
+                if n.inject(amount, event.time) {
+                    let spike = SpikeEvent::spike(event.neuron_id, event.time).with_offset(event.offset);
+                    let _ = self.queue.schedule_clamped(spike);
+                }
+            }
+            EventKind::SetParam(param, value) => n.set_param(param, value),
+            EventKind::Spike | EventKind::Reward(_) | EventKind::Marker(_) => {}
+        }
     }
 
     /// Advance the simulation by one tick (consumes the current slot of the time wheel)
-    /// and returns the spikes that occurred in this tick (the events popped from the wheel).
+    /// and returns the spikes that occurred in this tick (the events popped from the wheel,
+    /// including control events; filter with `SpikeEvent::is_spike`).
     /// Newly generated spikes are scheduled for future ticks but not returned here, so the
-    /// caller can treat the return value as "spikes at current time".
+    /// caller can treat the return value as "spikes at current time". Spikes of zero-delay
+    /// edges occur on the next tick; spikes beyond the wheel horizon are dropped (see `validate`).
     pub fn step_once(&mut self) -> Vec<SpikeEvent> {
         let events = self.queue.next(); // advances current_time internally
+        let two_phase = self.step_mode == StepMode::TwoPhase;
+
+        // Control events take effect before this tick's deliveries
+        for ev in events.iter().filter(|ev| !ev.is_spike()) {
+            self.apply_control(*ev);
+        }
 
         // Deliver effects of spikes from this tick, scheduling any resulting spikes
-        // at their (possibly future) delivery time.
-        for ev in &events {
-            // Deliver along any hyperedge that includes this source neuron
-            for edge in &self.edges {
-                // Naive scan for now (zero-deps). Could index by source->edges later.
-                if !edge.sources.iter().any(|&s| s == ev.neuron_id) {
-                    continue;
-                }
+        // at their (possibly future) delivery time, along the source's outgoing edges.
+        self.compile();
+        let edges = Arc::clone(&self.edges);
+        for ev in events.iter().filter(|ev| ev.is_spike()) {
+            for edge in edges.fan_out(ev.neuron_id) {
+                self.deliver(ev, edge.targets, edge.weight, edge.delay, edge.kind, two_phase);
+            }
+        }
 
-                let deliver_time = ev.time.saturating_add(edge.delay);
-
-                for &tgt in &edge.targets {
-                    if let Some(n) = self.neurons.get_mut(tgt as usize) {
-                        let fired = n.inject(edge.weight, deliver_time);
-                        if fired {
-                            let spike = SpikeEvent { neuron_id: tgt, time: deliver_time };
-                            // schedule the spike event at its time (may be current or future slot)
-                            self.queue.schedule(spike);
-                        }
-                    }
+        // Two-phase: threshold once per target on the summed input
+        if two_phase && !self.accumulator.is_empty() {
+            for (time, tgt, input) in self.accumulator.drain_sorted() {
+                if self.neurons[tgt as usize].inject(input, time) {
+                    let _ = self.queue.schedule_clamped(SpikeEvent::spike(tgt, time));
                 }
             }
         }
@@ -70,10 +333,62 @@ impl SnnRuntime {
         events
     }
 
+    fn deliver(&mut self, ev: &SpikeEvent, targets: &[NeuronId], weight: Fixed, delay: u64, kind: EdgeKind, two_phase: bool) {
+        let deliver_time = ev.time.saturating_add(delay);
+        if let EdgeKind::Modulatory(m) = kind {
+            for &tgt in targets {
+                if let Some(n) = self.neurons.get_mut(tgt as usize) {
+                    n.modulate(m, weight);
+                }
+            }
+            return;
+        }
+        if two_phase {
+            for &tgt in targets {
+                if (tgt as usize) < self.neurons.len() {
+                    self.accumulator.add(deliver_time, tgt, weight);
+                }
+            }
+            return;
+        }
+        for &tgt in targets {
+            if let Some(n) = self.neurons.get_mut(tgt as usize) {
+                if n.inject(weight, deliver_time) {
+                    // Integrate-and-fire crosses threshold on arrival: keep the offset
+                    let spike = SpikeEvent::spike(tgt, deliver_time).with_offset(ev.offset);
+                    // schedule the spike event at its time (next tick at the earliest)
+                    let _ = self.queue.schedule_clamped(spike);
+                }
+            }
+        }
+    }
+
     /// Run until the given tick (inclusive). Does not return emitted spikes.
+    /// Neurons have no per-tick dynamics, so ticks without events are skipped outright.
     pub fn run_until(&mut self, until: u64) {
         while self.queue.current_time <= until {
-            let _ = self.step_once();
+            if self.queue.skip_to(until.saturating_add(1)) > until {
+                break;
+            }
+            let events = self.step_once();
+            self.queue.recycle(events);
+        }
+    }
+
+    /// Step tick by tick until one of `conditions` holds (see `StopCondition`) and report
+    /// which one did. Runs forever if none ever holds, so include a `MaxTicks` safeguard.
+    pub fn run_until_condition(&mut self, conditions: &[StopCondition]) -> RunSummary {
+        let mut tracker = StopTracker::new(conditions);
+        let mut events = Vec::new();
+        loop {
+            if let Some(index) = tracker.check(&events, self.queue.is_empty()) {
+                self.queue.recycle(events);
+                return tracker.summary(index);
+            }
+            let tick = self.queue.current_time;
+            let next = self.step_once();
+            tracker.record_tick(tick, &next);
+            self.queue.recycle(core::mem::replace(&mut events, next));
         }
     }
 
@@ -82,4 +397,31 @@ impl SnnRuntime {
         let until = self.queue.current_time.saturating_add(ticks);
         self.run_until(until);
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_runtime_indexes_edges_added_between_steps() {
+        let mut rt = SnnRuntime::new(16);
+        for _ in 0..4 {
+            rt.add_neuron(1.0);
+        }
+        // A source listed twice delivers once
+        rt.add_edge(vec![0, 0], vec![1], 0.25, 1);
+        rt.queue.schedule(SpikeEvent::spike(0, 0)).unwrap();
+        rt.step_once();
+        assert!(rt.edges().is_compiled());
+        assert_eq!(rt.neurons[1].membrane, to_fixed(0.25));
+
+        // The next step indexes the new edge before delivering
+        rt.add_edge(vec![0], vec![2, 3], 0.5, 1);
+        assert!(!rt.edges().is_compiled());
+        rt.queue.schedule(SpikeEvent::spike(0, 1)).unwrap();
+        rt.step_once();
+        let membranes: Vec<_> = rt.neurons.iter().map(|n| n.membrane).collect();
+        assert_eq!(membranes, vec![0, to_fixed(0.5), to_fixed(0.5), to_fixed(0.5)]);
+    }
+}
@@ -10,7 +10,7 @@ pub struct CsrMatrix {
 
 impl CsrMatrix {
     pub fn new(row_ptr: Vec<usize>, col_idx: Vec<usize>, values: Vec<i32>) -> Self {
-        debug_assert!(row_ptr.len() >= 1);
+        debug_assert!(!row_ptr.is_empty());
         debug_assert!(col_idx.len() == values.len());
         Self { row_ptr, col_idx, values }
     }
@@ -19,7 +19,7 @@ impl CsrMatrix {
     pub fn mul_vector(&self, x: &[i32]) -> Vec<i32> {
         let rows = self.row_ptr.len() - 1;
         let mut result = vec![0i32; rows];
-        for r in 0..rows {
+        for (r, out) in result.iter_mut().enumerate() {
             let start = self.row_ptr[r];
             let end = self.row_ptr[r + 1];
             let mut acc: i64 = 0;
@@ -27,7 +27,7 @@ impl CsrMatrix {
                 let c = self.col_idx[k];
                 acc += (self.values[k] as i64) * (x[c] as i64);
             }
-            result[r] = (acc >> FRACTIONAL_BITS) as i32; // rescale from fixed-point mul
+            *out = (acc >> FRACTIONAL_BITS) as i32; // rescale from fixed-point mul
         }
         result
     }
@@ -0,0 +1,86 @@
+//! Stop conditions for `run_until_condition`
+
+use core::ops::Range;
+
+use crate::event_queue::SpikeEvent;
+use crate::neuron::NeuronId;
+
+/// When a run should end. Conditions are checked in order before the first tick and after
+/// every tick; the first that holds stops the run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StopCondition {
+    /// Nothing left to deliver (empty wheel; in SnnRuntimePlus also no deferred work and
+    /// idle synapse kernels)
+    Quiescent,
+    /// The neuron spiked
+    NeuronFired(NeuronId),
+    /// More than `threshold` spikes in one tick from neurons in `population`
+    ActivityAbove { population: Range<NeuronId>, threshold: usize },
+    /// Fewer than `threshold` spikes in one tick from neurons in `population`
+    ActivityBelow { population: Range<NeuronId>, threshold: usize },
+    /// At least this many spikes since the run started
+    SpikeCount(u64),
+    /// This many ticks have run (safeguard: include it unless another condition is certain)
+    MaxTicks(u64),
+}
+
+/// Outcome of `run_until_condition`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunSummary {
+    /// Index into the condition list of the condition that ended the run
+    pub index: usize,
+    pub condition: StopCondition,
+    /// Ticks run
+    pub ticks: u64,
+    /// Spikes returned by those ticks (control events are not counted)
+    pub spikes: u64,
+    /// Last tick run, if any
+    pub last_tick: Option<u64>,
+}
+
+/// Tracks a run against its stop conditions (shared by SnnRuntime and SnnRuntimePlus).
+pub struct StopTracker<'a> {
+    conditions: &'a [StopCondition],
+    ticks: u64,
+    spikes: u64,
+    last_tick: Option<u64>,
+}
+
+impl<'a> StopTracker<'a> {
+    pub fn new(conditions: &'a [StopCondition]) -> Self {
+        Self { conditions, ticks: 0, spikes: 0, last_tick: None }
+    }
+
+    /// Count the spikes of a tick that just ran.
+    pub fn record_tick(&mut self, tick: u64, events: &[SpikeEvent]) {
+        self.ticks += 1;
+        self.spikes += events.iter().filter(|e| e.is_spike()).count() as u64;
+        self.last_tick = Some(tick);
+    }
+
+    /// First condition that holds given the last tick's events (empty before the first
+    /// tick) and whether the runtime is quiescent.
+    pub fn check(&self, events: &[SpikeEvent], quiescent: bool) -> Option<usize> {
+        let active = |population: &Range<NeuronId>| {
+            events.iter().filter(|e| e.is_spike() && population.contains(&e.neuron_id)).count()
+        };
+        self.conditions.iter().position(|c| match c {
+            StopCondition::Quiescent => quiescent,
+            StopCondition::NeuronFired(n) => events.iter().any(|e| e.is_spike() && e.neuron_id == *n),
+            StopCondition::ActivityAbove { population, threshold } => active(population) > *threshold,
+            StopCondition::ActivityBelow { population, threshold } => self.ticks > 0 && active(population) < *threshold,
+            StopCondition::SpikeCount(n) => self.spikes >= *n,
+            StopCondition::MaxTicks(max) => self.ticks >= *max,
+        })
+    }
+
+    pub fn summary(&self, index: usize) -> RunSummary {
+        RunSummary {
+            index,
+            condition: self.conditions[index].clone(),
+            ticks: self.ticks,
+            spikes: self.spikes,
+            last_tick: self.last_tick,
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Physical time base: tick duration and millisecond <-> tick conversion
+
+use crate::event_queue::SpikeEvent;
+
+const NS_PER_MS: f64 = 1_000_000.0;
+
+/// Duration of one tick, in nanoseconds (default 1 ms).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeBase {
+    tick_ns: u64,
+}
+
+impl Default for TimeBase {
+    fn default() -> Self {
+        Self { tick_ns: 1_000_000 }
+    }
+}
+
+/// A millisecond value that was not a whole number of ticks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoundingWarning {
+    pub requested_ms: f64,
+    /// What the rounded tick count amounts to
+    pub actual_ms: f64,
+}
+
+impl TimeBase {
+    /// Tick duration in nanoseconds (at least 1).
+    pub fn from_tick_ns(tick_ns: u64) -> Self {
+        Self { tick_ns: tick_ns.max(1) }
+    }
+
+    /// Tick duration in milliseconds, e.g. 0.1 for 10 kHz stepping (rounded to whole ns).
+    pub fn from_tick_ms(tick_ms: f64) -> Self {
+        Self::from_tick_ns((tick_ms * NS_PER_MS).round() as u64)
+    }
+
+    /// Time base matching an embedded network stepped with `dt` seconds per update.
+    pub fn from_dt_seconds(dt: f64) -> Self {
+        Self::from_tick_ms(dt * 1000.0)
+    }
+
+    pub fn tick_ns(&self) -> u64 {
+        self.tick_ns
+    }
+
+    pub fn tick_ms(&self) -> f64 {
+        self.tick_ns as f64 / NS_PER_MS
+    }
+
+    pub fn ticks_to_ms(&self, ticks: u64) -> f64 {
+        ticks as f64 * self.tick_ms()
+    }
+
+    pub fn ticks_to_seconds(&self, ticks: u64) -> f64 {
+        self.ticks_to_ms(ticks) / 1000.0
+    }
+
+    /// Physical time of an event, including its sub-tick offset.
+    pub fn event_ms(&self, event: &SpikeEvent) -> f64 {
+        event.exact_time() * self.tick_ms()
+    }
+
+    /// Nearest whole tick count for `ms` (negative values become 0), with a warning when
+    /// rounding changed the duration by more than half a nanosecond.
+    pub fn ms_to_ticks(&self, ms: f64) -> (u64, Option<RoundingWarning>) {
+        let ns = ms.max(0.0) * NS_PER_MS;
+        let ticks = (ns / self.tick_ns as f64).round() as u64;
+        let actual_ms = self.ticks_to_ms(ticks);
+        let exact = ms >= 0.0 && (ticks as f64 * self.tick_ns as f64 - ns).abs() <= 0.5;
+        (ticks, (!exact).then_some(RoundingWarning { requested_ms: ms, actual_ms }))
+    }
+
+    /// A time constant in ms expressed in (fractional) ticks; no rounding involved.
+    pub fn tau_ticks(&self, tau_ms: f32) -> f32 {
+        (f64::from(tau_ms) / self.tick_ms()) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_base_converts_ms_and_warns_on_rounding() {
+        let tb = TimeBase::from_tick_ms(0.1);
+        assert_eq!(tb.ms_to_ticks(1.5), (15, None));
+        let (ticks, warning) = tb.ms_to_ticks(0.25);
+        assert_eq!(ticks, 3);
+        let warning = warning.unwrap();
+        assert_eq!(warning.requested_ms, 0.25);
+        assert!((warning.actual_ms - 0.3).abs() < 1e-9);
+
+        assert!((tb.ticks_to_ms(10) - 1.0).abs() < 1e-9);
+        assert!((tb.event_ms(&SpikeEvent::spike(0, 10).with_offset(0x8000)) - 1.05).abs() < 1e-9);
+        assert!((tb.tau_ticks(2.0) - 20.0).abs() < 1e-4);
+        // Matches an embedded network stepped with dt = 0.1 ms
+        assert_eq!(TimeBase::from_dt_seconds(0.0001), tb);
+    }
+}
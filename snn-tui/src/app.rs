@@ -11,8 +11,6 @@ pub struct App<B: SnnBackend> {
     pub raster: Vec<Vec<char>>,   // [neuron][col]
     pub running: bool,
     pub budgets: Option<StepBudgets>,
-    #[cfg(feature = "plasticity")]
-    pub plast_on: bool,
 }
 
 impl<B: SnnBackend> App<B> {
@@ -25,8 +23,6 @@ impl<B: SnnBackend> App<B> {
             raster: vec![vec![' '; width]; n],
             running: false,
             budgets: None,
-            #[cfg(feature = "plasticity")]
-            plast_on: false,
         }
     }
 
@@ -1,7 +1,7 @@
 // Backend abstraction for the TUI so we can swap different SNN engines.
 
 use snn_core::SpikeEvent;
-use snn_core_plus::{SnnRuntimePlus, StepBudgets};
+use snn_core_plus::{SnnRuntimePlus, StepBudgets, StepReport};
 
 /// Common interface for any SNN backend that can drive the TUI.
 pub trait SnnBackend {
@@ -13,12 +13,12 @@ pub trait SnnBackend {
     /// Configure per-tick processing budgets (None = unbounded). Default no-op for backends that ignore budgets.
     fn set_budgets(&mut self, _budgets: Option<StepBudgets>) {}
 
-    /// Query current budgets (None if unbounded). Default None for backends that ignore budgets.
-    fn get_budgets(&self) -> Option<StepBudgets> { None }
+    /// Telemetry of the last step (spikes omitted), if the backend collects it.
+    fn last_report(&self) -> Option<&StepReport> { None }
 
     /// Optional plasticity controls (feature-gated); default no-ops/reports disabled.
     #[cfg(feature = "plasticity")]
-    fn enable_default_plasticity(&mut self) {}
+    fn set_plasticity_enabled(&mut self, _on: bool) {}
 
     #[cfg(feature = "plasticity")]
     fn plasticity_enabled(&self) -> bool { false }
@@ -28,53 +28,36 @@ pub trait SnnBackend {
 pub struct CoreBackend {
     runtime: SnnRuntimePlus,
     budgets: Option<StepBudgets>,
-    #[cfg(feature = "plasticity")]
-    plast_on: bool,
+    last_report: Option<StepReport>,
 }
 
 impl CoreBackend {
     pub fn new() -> Self {
         // Simple 3-neuron demo network
-        let mut rt = SnnRuntimePlus::new(32);
+        let mut rt = SnnRuntimePlus::auto_sized();
         let n0 = rt.add_neuron(1.0);
         let n1 = rt.add_neuron(1.0);
         let n2 = rt.add_neuron(1.0);
         rt.add_edge(vec![n0], vec![n1, n2], 1.0, 1);
 
         // Seed an initial spike at time 0
-        rt.queue().schedule(SpikeEvent { neuron_id: n0, time: 0 });
+        rt.queue().schedule(SpikeEvent::spike(n0, 0)).expect("time 0 is within the horizon");
 
         Self {
             runtime: rt,
             budgets: None,
-            #[cfg(feature = "plasticity")]
-            plast_on: false,
+            last_report: None,
         }
     }
-
-    /// Configure per-tick processing budgets (None = unbounded)
-    pub fn set_budgets(&mut self, budgets: Option<StepBudgets>) {
-        self.budgets = budgets;
-    }
-
-    /// Access the inner time wheel (for advanced seeding/scheduling)
-    pub fn queue_mut(&mut self) -> &mut snn_core::TimeWheel {
-        self.runtime.queue()
-    }
-
-    #[cfg(feature = "plasticity")]
-    /// Enable default plasticity (Quantized STDP)
-    pub fn enable_default_plasticity(&mut self) {
-        self.runtime.set_plasticity(snn_core_plus::QuantizedStdp::with_defaults());
-    }
 }
 
 impl SnnBackend for CoreBackend {
     fn step(&mut self) -> Vec<SpikeEvent> {
-        match self.budgets {
-            Some(b) => self.runtime.step_once_with_budgets(b),
-            None => self.runtime.step_once(),
-        }
+        let mut report = self.runtime.step_with_report(self.budgets.unwrap_or_default());
+        let mut spikes = std::mem::take(&mut report.spikes);
+        spikes.retain(SpikeEvent::is_spike);
+        self.last_report = Some(report);
+        spikes
     }
 
     fn neurons(&self) -> usize {
@@ -85,20 +68,23 @@ impl SnnBackend for CoreBackend {
         self.budgets = budgets;
     }
 
-    fn get_budgets(&self) -> Option<StepBudgets> {
-        self.budgets
+    fn last_report(&self) -> Option<&StepReport> {
+        self.last_report.as_ref()
     }
 
+    /// Install default plasticity (Quantized STDP) on first enable; afterwards pause/resume it.
     #[cfg(feature = "plasticity")]
-    fn enable_default_plasticity(&mut self) {
-        if !self.plast_on {
+    fn set_plasticity_enabled(&mut self, on: bool) {
+        let stack = self.runtime.plasticity_mut();
+        if stack.is_empty() && on {
             self.runtime.set_plasticity(snn_core_plus::QuantizedStdp::with_defaults());
-            self.plast_on = true;
+        } else {
+            stack.set_all_enabled(on);
         }
     }
 
     #[cfg(feature = "plasticity")]
     fn plasticity_enabled(&self) -> bool {
-        self.plast_on
+        self.runtime.plasticity().is_active()
     }
 }
\ No newline at end of file
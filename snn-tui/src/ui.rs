@@ -53,20 +53,40 @@ pub fn draw<B: SnnBackend>(
         f.render_widget(raster_widget, chunks[0]);
 
         // Status and controls with budgets/plasticity info
-        let budgets = app.budgets.unwrap_or(snn_core_plus::StepBudgets { max_edge_visits: None, max_spikes_scheduled: None });
+        let budgets = app.budgets.unwrap_or_default();
         let bev = budgets.max_edge_visits.map(|v| v.to_string()).unwrap_or("-".to_string());
         let bss = budgets.max_spikes_scheduled.map(|v| v.to_string()).unwrap_or("-".to_string());
         #[cfg(feature = "plasticity")]
-        let plast = if app.plast_on { "on" } else { "off" };
+        let plast = if app.backend.plasticity_enabled() { "on" } else { "off" };
         #[cfg(not(feature = "plasticity"))]
         let plast = "n/a";
 
+        let last = match app.backend.last_report() {
+            Some(r) => format!(
+                "Last: t={:.1}ms edges={} inj={} sched={} defer={} drop={} plast={}{}",
+                r.time_ms,
+                r.edges_visited,
+                r.targets_injected,
+                r.spikes_scheduled,
+                r.deferred,
+                r.dropped,
+                r.plasticity_updates,
+                match (r.edge_budget_hit, r.spike_budget_hit) {
+                    (true, true) => " [budget: edges+spikes]",
+                    (true, false) => " [budget: edges]",
+                    (false, true) => " [budget: spikes]",
+                    (false, false) => "",
+                }
+            ),
+            None => "Last: -".to_string(),
+        };
+
         let status = format!(
-            "Tick: {} | Neurons: {} | Running: {} | Budgets: edges={} spikes={} | Plasticity: {} | Controls: [s] Step  [r] Run/Pause  [+/-] edges  [[]/] spikes  [p] plast  [q] Quit",
+            "Tick: {} | Neurons: {} | Running: {} | Budgets: edges={} spikes={} | Plasticity: {} | {} | Controls: [s] Step  [r] Run/Pause  [+/-] edges  [[]/] spikes  [p] plast  [q] Quit",
             app.tick,
             app.backend.neurons(),
             if app.running { "yes" } else { "no" },
-            bev, bss, plast
+            bev, bss, plast, last
         );
         let status_widget = Paragraph::new(status)
             .style(Style::default().fg(Color::Cyan))